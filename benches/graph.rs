@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
 use ps_algorithm::data_structure::Graph;
+use ps_algorithm::graph::{self, Visitor};
 use rand::{thread_rng, Rng};
 
 fn make_tree(n: usize) -> Graph<()> {
@@ -35,6 +36,14 @@ fn dfs(visited: &mut [bool], root: usize, graph: &Graph<()>) {
     }
 }
 
+struct BlackBox;
+
+impl Visitor<()> for BlackBox {
+    fn enter(&mut self, node: usize) {
+        black_box(node);
+    }
+}
+
 pub fn graph_dfs(c: &mut Criterion) {
     let mut group = c.benchmark_group("graph_dfs");
     for n in [100, 1000, 10000, 100000] {
@@ -67,5 +76,25 @@ pub fn graph_dfs(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, graph_dfs);
+pub fn graph_iterative_dfs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph_iterative_dfs");
+    for n in [100, 1000, 10000, 100000, 1000000] {
+        let tree = make_tree(n);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{} tree", n)),
+            &tree,
+            |b, tree| b.iter(|| graph::dfs(tree, [0], &mut BlackBox)),
+        );
+    }
+    for n in [10, 100, 1000] {
+        let complete = make_complete_graph(n);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{} complete", n)),
+            &complete,
+            |b, complete| b.iter(|| graph::dfs(complete, [0], &mut BlackBox)),
+        );
+    }
+}
+
+criterion_group!(benches, graph_dfs, graph_iterative_dfs);
 criterion_main!(benches);
@@ -0,0 +1,160 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Lock-free disjoint set forest shareable across threads.
+// Roots are linked by index, the smaller under the larger, so no cycle can form
+// between concurrent joins. Paths are halved with CAS, ignoring failures.
+pub struct ConcurrentSetForest {
+    parent: Vec<AtomicUsize>,
+    count: AtomicUsize,
+}
+
+impl ConcurrentSetForest {
+    pub fn new(s: usize) -> Self {
+        Self {
+            parent: (0..s).map(AtomicUsize::new).collect(),
+            count: AtomicUsize::new(s),
+        }
+    }
+
+    pub fn find(&self, mut i: usize) -> usize {
+        loop {
+            let p = self.parent[i].load(Ordering::Acquire);
+            if p == i {
+                return i;
+            }
+            let gp = self.parent[p].load(Ordering::Acquire);
+            if p != gp {
+                // Path-halving
+                let _ = self.parent[i].compare_exchange_weak(
+                    p,
+                    gp,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                );
+            }
+            i = gp;
+        }
+    }
+
+    // Returns if two items were in the same set.
+    pub fn join(&self, mut u: usize, mut v: usize) -> bool {
+        loop {
+            u = self.find(u);
+            v = self.find(v);
+            if u == v {
+                return true;
+            }
+            let (child, root) = if u < v { (u, v) } else { (v, u) };
+            if self.parent[child]
+                .compare_exchange(child, root, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                self.count.fetch_sub(1, Ordering::AcqRel);
+                return false;
+            }
+        }
+    }
+
+    pub fn same(&self, mut u: usize, mut v: usize) -> bool {
+        loop {
+            u = self.find(u);
+            v = self.find(v);
+            if u == v {
+                return true;
+            }
+            // u was still a root after v was found, so they were apart at that moment.
+            if self.parent[u].load(Ordering::Acquire) == u {
+                return false;
+            }
+        }
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_structure::SetForest;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn join() {
+        let forest = ConcurrentSetForest::new(5);
+        assert!(!forest.join(1, 3));
+        assert!(!forest.join(2, 4));
+        assert!(!forest.join(0, 3));
+        assert!(forest.join(0, 1));
+        assert_eq!(2, forest.component_count());
+        assert!(forest.same(0, 3));
+        assert!(!forest.same(0, 4));
+    }
+
+    #[test]
+    fn concurrent_join() {
+        let n = 10000;
+        let edges: Vec<Vec<(usize, usize)>> = (0..8)
+            .map(|seed| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                (0..2000)
+                    .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                    .collect()
+            })
+            .collect();
+        let forest = ConcurrentSetForest::new(n);
+        let merged: usize = std::thread::scope(|s| {
+            let handles: Vec<_> = edges
+                .iter()
+                .map(|edges| {
+                    let forest = &forest;
+                    s.spawn(move || edges.iter().filter(|&&(u, v)| !forest.join(u, v)).count())
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+
+        let mut expected = SetForest::new(n);
+        for &(u, v) in edges.iter().flatten() {
+            expected.join(u, v);
+        }
+        assert_eq!(expected.component_count(), forest.component_count());
+        assert_eq!(n - merged, forest.component_count());
+        for &(u, v) in edges.iter().flatten() {
+            assert!(forest.same(u, v));
+        }
+        for u in (0..n).step_by(97) {
+            for v in (0..n).step_by(89) {
+                assert_eq!(expected.same(u, v), forest.same(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn concurrent_same() {
+        let n = 1000;
+        let forest = ConcurrentSetForest::new(n);
+        std::thread::scope(|s| {
+            // Joins only even items together, while others keep checking
+            // that odd items never join anything.
+            s.spawn(|| {
+                for i in (2..n).step_by(2) {
+                    forest.join(i - 2, i);
+                }
+            });
+            for seed in 0..4 {
+                let forest = &forest;
+                s.spawn(move || {
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    for _ in 0..10000 {
+                        let u = rng.gen_range(0..n / 2) * 2 + 1;
+                        let v = rng.gen_range(0..n);
+                        assert_eq!(u == v, forest.same(u, v));
+                    }
+                });
+            }
+        });
+        assert_eq!(n / 2 + 1, forest.component_count());
+    }
+}
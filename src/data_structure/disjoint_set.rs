@@ -1,13 +1,17 @@
+use crate::math::{CommutativeMonoid, Monoid};
+
 pub struct SetForest {
     parent: Vec<usize>,
-    rank: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
 }
 
 impl SetForest {
     pub fn new(s: usize) -> Self {
         Self {
             parent: (0..s).collect(),
-            rank: vec![1; s],
+            size: vec![1; s],
+            count: s,
         }
     }
 
@@ -20,6 +24,14 @@ impl SetForest {
         i
     }
 
+    // Same as find() without path compression, O(log n) by union by size.
+    pub fn root(&self, mut i: usize) -> usize {
+        while i != self.parent[i] {
+            i = self.parent[i];
+        }
+        i
+    }
+
     // Returns if two items were in the same set.
     pub fn join(&mut self, u: usize, v: usize) -> bool {
         let mut pu = self.find(u);
@@ -27,16 +39,106 @@ impl SetForest {
         if pu == pv {
             true
         } else {
-            if self.rank[pu] < self.rank[pv] {
+            if self.size[pu] < self.size[pv] {
                 std::mem::swap(&mut pu, &mut pv);
             }
             self.parent[pv] = pu;
-            if self.rank[pu] == self.rank[pv] {
-                self.rank[pu] += 1;
-            }
+            self.size[pu] += self.size[pv];
+            self.count -= 1;
             false
         }
     }
+
+    pub fn same(&self, u: usize, v: usize) -> bool {
+        self.root(u) == self.root(v)
+    }
+
+    // Size of the set containing i
+    pub fn size(&self, i: usize) -> usize {
+        self.size[self.root(i)]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+
+    // All sets, ordered by their smallest items
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.parent.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+        for i in 0..self.parent.len() {
+            let root = self.root(i);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[index[root]].push(i);
+        }
+        groups
+    }
+}
+
+// SetForest carrying a value per set, combined by M when two sets are joined.
+pub struct AggregateSetForest<M: Monoid> {
+    forest: SetForest,
+    val: Vec<M::T>,
+}
+
+impl<M: Monoid> AggregateSetForest<M> {
+    pub fn new(data: impl IntoIterator<Item = M::T>) -> Self {
+        let val: Vec<M::T> = data.into_iter().collect();
+        Self {
+            forest: SetForest::new(val.len()),
+            val,
+        }
+    }
+
+    pub fn forest(&self) -> &SetForest {
+        &self.forest
+    }
+
+    pub fn find(&mut self, i: usize) -> usize {
+        self.forest.find(i)
+    }
+
+    // Joins with merge(value of the larger set, value of the smaller set),
+    // e.g. for small-to-large merging without cloning.
+    pub fn join_with(
+        &mut self,
+        u: usize,
+        v: usize,
+        merge: impl FnOnce(M::T, M::T) -> M::T,
+    ) -> bool {
+        let pu = self.forest.find(u);
+        let pv = self.forest.find(v);
+        if self.forest.join(pu, pv) {
+            return true;
+        }
+        let root = self.forest.find(pu);
+        let child = if root == pu { pv } else { pu };
+        let large = std::mem::replace(&mut self.val[root], M::ident());
+        let small = std::mem::replace(&mut self.val[child], M::ident());
+        self.val[root] = merge(large, small);
+        false
+    }
+
+    // Value of the set containing i
+    pub fn get(&self, i: usize) -> &M::T {
+        &self.val[self.forest.root(i)]
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> &mut M::T {
+        let root = self.forest.find(i);
+        &mut self.val[root]
+    }
+}
+
+// The operand order of a join depends on the set sizes.
+impl<M: CommutativeMonoid> AggregateSetForest<M> {
+    // Returns if two items were in the same set.
+    pub fn join(&mut self, u: usize, v: usize) -> bool {
+        self.join_with(u, v, |l, r| M::apply(&l, &r))
+    }
 }
 
 #[cfg(test)]
@@ -46,21 +148,21 @@ mod test {
     #[test]
     fn join() {
         let mut forest = SetForest::new(5);
-        assert_eq!(false, forest.join(1, 3));
+        assert!(!forest.join(1, 3));
         assert_eq!(vec![0, 1, 2, 1, 4], forest.parent);
-        assert_eq!(vec![1, 2, 1, 1, 1], forest.rank);
-        assert_eq!(false, forest.join(2, 4));
+        assert_eq!(vec![1, 2, 1, 1, 1], forest.size);
+        assert!(!forest.join(2, 4));
         assert_eq!(vec![0, 1, 2, 1, 2], forest.parent);
-        assert_eq!(vec![1, 2, 2, 1, 1], forest.rank);
-        assert_eq!(false, forest.join(0, 3));
+        assert_eq!(vec![1, 2, 2, 1, 1], forest.size);
+        assert!(!forest.join(0, 3));
         assert_eq!(vec![1, 1, 2, 1, 2], forest.parent);
-        assert_eq!(vec![1, 2, 2, 1, 1], forest.rank);
-        assert_eq!(true, forest.join(0, 1));
+        assert_eq!(vec![1, 3, 2, 1, 1], forest.size);
+        assert!(forest.join(0, 1));
         assert_eq!(vec![1, 1, 2, 1, 2], forest.parent);
-        assert_eq!(vec![1, 2, 2, 1, 1], forest.rank);
-        assert_eq!(false, forest.join(1, 4));
+        assert_eq!(vec![1, 3, 2, 1, 1], forest.size);
+        assert!(!forest.join(1, 4));
         assert_eq!(vec![1, 1, 1, 1, 2], forest.parent);
-        assert_eq!(vec![1, 3, 2, 1, 1], forest.rank);
+        assert_eq!(vec![1, 5, 2, 1, 1], forest.size);
     }
 
     #[test]
@@ -74,4 +176,74 @@ mod test {
         forest.join(0, 2);
         assert_eq!(0, forest.find(3));
     }
+
+    #[test]
+    fn groups() {
+        let mut forest = SetForest::new(6);
+        forest.join(4, 1);
+        forest.join(2, 5);
+        forest.join(1, 2);
+        assert_eq!(3, forest.component_count());
+        assert_eq!(4, forest.size(5));
+        assert_eq!(1, forest.size(3));
+        assert!(forest.same(4, 5));
+        assert!(!forest.same(0, 5));
+        assert_eq!(vec![vec![0], vec![1, 2, 4, 5], vec![3]], forest.groups());
+    }
+
+    struct Sum;
+    impl Monoid for Sum {
+        type T = i64;
+        fn apply(l: &i64, r: &i64) -> i64 {
+            l + r
+        }
+        fn ident() -> i64 {
+            0
+        }
+    }
+    impl CommutativeMonoid for Sum {}
+
+    struct Union;
+    impl Monoid for Union {
+        type T = Vec<usize>;
+        fn apply(l: &Vec<usize>, r: &Vec<usize>) -> Vec<usize> {
+            let mut merged = l.clone();
+            merged.extend(r);
+            merged
+        }
+        fn ident() -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn aggregate() {
+        let mut forest = AggregateSetForest::<Sum>::new([5, 1, 4, 2, 3]);
+        forest.join(0, 2);
+        forest.join(3, 4);
+        assert_eq!(9, *forest.get(2));
+        assert_eq!(1, *forest.get(1));
+        forest.join(4, 2);
+        *forest.get_mut(1) += 10;
+        assert_eq!(14, *forest.get(0));
+        assert_eq!(11, *forest.get(1));
+        assert!(forest.join(3, 0));
+        assert_eq!(14, *forest.get(0));
+    }
+
+    #[test]
+    fn aggregate_small_to_large() {
+        let mut forest = AggregateSetForest::<Union>::new((0..5).map(|i| vec![i]));
+        let merge = |mut large: Vec<usize>, small: Vec<usize>| {
+            large.extend(small);
+            large
+        };
+        forest.join_with(0, 2, merge);
+        forest.join_with(3, 4, merge);
+        forest.join_with(2, 4, merge);
+        let mut members = forest.get(3).clone();
+        members.sort_unstable();
+        assert_eq!(vec![0, 2, 3, 4], members);
+        assert_eq!(&vec![1], forest.get(1));
+    }
 }
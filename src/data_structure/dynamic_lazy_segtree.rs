@@ -0,0 +1,157 @@
+use crate::data_structure::lazy_segtree::Operation;
+
+// Lazy segment tree over [lo, hi) whose nodes are created on first modification.
+// Untouched elements are i_value(), and hi - lo must fit in i64.
+pub struct DynamicLazySegmentTree<O: Operation> {
+    val: Vec<O::Value>,
+    lazy: Vec<O::Update>,
+    child: Vec<[u32; 2]>,
+    lo: i64,
+    hi: i64,
+}
+
+impl<O: Operation> DynamicLazySegmentTree<O> {
+    // Node 0 is a sentinel for absent children, node 1 is the root.
+    pub fn new(lo: i64, hi: i64) -> Self {
+        Self {
+            val: vec![O::i_value(); 2],
+            lazy: vec![O::i_update(); 2],
+            child: vec![[0, 0]; 2],
+            lo,
+            hi,
+        }
+    }
+
+    fn apply(&mut self, node: u32, len: usize, amount: &O::Update) {
+        O::apply(&mut self.val[node as usize], len, amount);
+        O::compose(&mut self.lazy[node as usize], amount);
+    }
+
+    fn push(&mut self, node: u32, s: i64, e: i64) -> [u32; 2] {
+        let node = node as usize;
+        for side in 0..2 {
+            if self.child[node][side] == 0 {
+                self.val.push(O::i_value());
+                self.lazy.push(O::i_update());
+                self.child.push([0, 0]);
+                self.child[node][side] = (self.val.len() - 1) as u32;
+            }
+        }
+        let [l, r] = self.child[node];
+        if !O::is_i(&self.lazy[node]) {
+            let amount = std::mem::replace(&mut self.lazy[node], O::i_update());
+            let m = s + (e - s) / 2;
+            self.apply(l, (m - s) as usize, &amount);
+            self.apply(r, (e - m) as usize, &amount);
+        }
+        [l, r]
+    }
+
+    pub fn modify(&mut self, l: i64, r: i64, amount: &O::Update) {
+        self.modify_at(1, self.lo, self.hi, l, r, amount);
+    }
+
+    fn modify_at(&mut self, node: u32, s: i64, e: i64, l: i64, r: i64, amount: &O::Update) {
+        if r <= s || e <= l {
+            return;
+        }
+        if l <= s && e <= r {
+            self.apply(node, (e - s) as usize, amount);
+            return;
+        }
+        let [lc, rc] = self.push(node, s, e);
+        let m = s + (e - s) / 2;
+        self.modify_at(lc, s, m, l, r, amount);
+        self.modify_at(rc, m, e, l, r, amount);
+        self.val[node as usize] = O::combine(&self.val[lc as usize], &self.val[rc as usize]);
+    }
+
+    // Pending updates are applied on the way back up, so no node is created.
+    pub fn query(&self, l: i64, r: i64) -> O::Value {
+        self.query_at(1, self.lo, self.hi, l, r)
+    }
+
+    fn query_at(&self, node: u32, s: i64, e: i64, l: i64, r: i64) -> O::Value {
+        if r <= s || e <= l {
+            return O::i_value();
+        }
+        let node = node as usize;
+        if l <= s && e <= r {
+            return self.val[node].clone();
+        }
+        if node == 0 {
+            return O::i_value();
+        }
+        let m = s + (e - s) / 2;
+        let [lc, rc] = self.child[node];
+        let mut result = O::combine(
+            &self.query_at(lc, s, m, l, r),
+            &self.query_at(rc, m, e, l, r),
+        );
+        if !O::is_i(&self.lazy[node]) {
+            let len = (e.min(r) - s.max(l)) as usize;
+            O::apply(&mut result, len, &self.lazy[node]);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct SumAdd;
+    impl Operation for SumAdd {
+        type Value = i64;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            l + r
+        }
+        fn i_value() -> Self::Value {
+            0
+        }
+        fn compose(a: &mut Self::Update, b: &Self::Update) {
+            *a += b
+        }
+        fn is_i(u: &Self::Update) -> bool {
+            *u == 0
+        }
+        fn i_update() -> Self::Update {
+            0
+        }
+        fn apply(v: &mut Self::Value, len: usize, u: &Self::Update) {
+            *v += len as i64 * u;
+        }
+    }
+
+    #[test]
+    fn query() {
+        let mut tree = DynamicLazySegmentTree::<SumAdd>::new(0, 1_000_000_000_000_000_000);
+        tree.modify(0, 1_000_000_000_000_000_000, &1);
+        tree.modify(500_000_000_000, 500_000_000_010, &-3);
+        assert_eq!(
+            999_999_999_999_999_970,
+            tree.query(0, 1_000_000_000_000_000_000)
+        );
+        assert_eq!(-2, tree.query(500_000_000_000, 500_000_000_001));
+        assert_eq!(499_999_999_995, tree.query(5, 500_000_000_000));
+    }
+
+    #[test]
+    fn modify() {
+        let mut tree = DynamicLazySegmentTree::<SumAdd>::new(-10, 10);
+        let mut data = [0; 20];
+        for (l, r, v) in [(-10, 10, 2), (-3, 4, -1), (0, 1, 7), (5, 10, 3), (-8, 6, 1)] {
+            tree.modify(l, r, &v);
+            data[(l + 10) as usize..(r + 10) as usize]
+                .iter_mut()
+                .for_each(|x| *x += v);
+        }
+        for l in -10..=10 {
+            for r in l..=10 {
+                let expected: i64 = data[(l + 10) as usize..(r + 10) as usize].iter().sum();
+                assert_eq!(expected, tree.query(l, r));
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::data_structure::segtree::Operation;
+
+// Segment tree over [lo, hi) whose nodes are created on first modification.
+// Untouched elements are i_value(), and hi - lo must fit in i64.
+pub struct DynamicSegmentTree<O: Operation> {
+    val: Vec<O::Value>,
+    child: Vec<[u32; 2]>,
+    lo: i64,
+    hi: i64,
+}
+
+impl<O: Operation> DynamicSegmentTree<O> {
+    // Node 0 is a sentinel for absent children, node 1 is the root.
+    pub fn new(lo: i64, hi: i64) -> Self {
+        Self {
+            val: vec![O::i_value(); 2],
+            child: vec![[0, 0]; 2],
+            lo,
+            hi,
+        }
+    }
+
+    pub fn modify(&mut self, i: i64, amount: &O::Update) {
+        assert!(self.lo <= i && i < self.hi);
+        let mut path = vec![];
+        let mut node = 1;
+        let mut s = self.lo;
+        let mut e = self.hi;
+        while e - s > 1 {
+            path.push(node);
+            let m = s + (e - s) / 2;
+            let side = if i < m {
+                e = m;
+                0
+            } else {
+                s = m;
+                1
+            };
+            if self.child[node][side] == 0 {
+                self.val.push(O::i_value());
+                self.child.push([0, 0]);
+                self.child[node][side] = (self.val.len() - 1) as u32;
+            }
+            node = self.child[node][side] as usize;
+        }
+        O::apply(&mut self.val[node], amount);
+        for &node in path.iter().rev() {
+            let [l, r] = self.child[node];
+            self.val[node] = O::combine(&self.val[l as usize], &self.val[r as usize]);
+        }
+    }
+
+    pub fn query(&self, l: i64, r: i64) -> O::Value {
+        self.query_at(1, self.lo, self.hi, l, r)
+    }
+
+    fn query_at(&self, node: u32, s: i64, e: i64, l: i64, r: i64) -> O::Value {
+        if node == 0 || r <= s || e <= l {
+            O::i_value()
+        } else if l <= s && e <= r {
+            self.val[node as usize].clone()
+        } else {
+            let m = s + (e - s) / 2;
+            let [lc, rc] = self.child[node as usize];
+            O::combine(
+                &self.query_at(lc, s, m, l, r),
+                &self.query_at(rc, m, e, l, r),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct SumAdd;
+    impl Operation for SumAdd {
+        type Value = i64;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            l + r
+        }
+        fn i_value() -> Self::Value {
+            0
+        }
+        fn apply(v: &mut Self::Value, u: &Self::Update) {
+            *v += *u;
+        }
+    }
+
+    #[test]
+    fn query() {
+        let mut tree = DynamicSegmentTree::<SumAdd>::new(0, 1_000_000_000_000_000_000);
+        tree.modify(0, &3);
+        tree.modify(999_999_999_999_999_999, &5);
+        tree.modify(123_456_789_012, &7);
+        tree.modify(123_456_789_012, &-2);
+        assert_eq!(13, tree.query(0, 1_000_000_000_000_000_000));
+        assert_eq!(3, tree.query(0, 123_456_789_012));
+        assert_eq!(8, tree.query(0, 123_456_789_013));
+        assert_eq!(10, tree.query(1, 1_000_000_000_000_000_000));
+        assert_eq!(0, tree.query(1, 123_456_789_012));
+    }
+
+    #[test]
+    fn negative_range() {
+        let mut tree = DynamicSegmentTree::<SumAdd>::new(-10, 10);
+        let mut data = [0; 20];
+        for (i, v) in [(-10, 4), (-3, 2), (0, 7), (9, 1), (-3, 5)] {
+            tree.modify(i, &v);
+            data[(i + 10) as usize] += v;
+        }
+        for l in -10..=10 {
+            for r in l..=10 {
+                let expected: i64 = data[(l + 10) as usize..(r + 10) as usize].iter().sum();
+                assert_eq!(expected, tree.query(l, r));
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::math::{CommutativeMonoid, Group, Monoid};
+
 pub trait Operation {
     type Value: Clone;
     type Update;
@@ -7,6 +9,32 @@ pub trait Operation {
     fn apply(v: &mut Self::Value, u: &Self::Update);
 }
 
+impl<M: CommutativeMonoid> Operation for M {
+    type Value = M::T;
+    type Update = M::T;
+
+    fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+        <M as Monoid>::apply(l, r)
+    }
+    fn i_value() -> Self::Value {
+        M::ident()
+    }
+    fn apply(v: &mut Self::Value, u: &Self::Update) {
+        *v = <M as Monoid>::apply(v, u);
+    }
+}
+
+pub trait Invertible: Operation {
+    // combine(v, inverse(v)) == i_value()
+    fn inverse(v: &Self::Value) -> Self::Value;
+}
+
+impl<G: CommutativeMonoid + Group> Invertible for G {
+    fn inverse(v: &Self::Value) -> Self::Value {
+        <G as Group>::inverse(v)
+    }
+}
+
 pub struct FenwickTree<O: Operation> {
     val: Vec<O::Value>,
     n: usize,
@@ -20,6 +48,10 @@ impl<O: Operation> FenwickTree<O> {
         }
     }
 
+    pub fn from_slice(data: &[O::Value]) -> Self {
+        data.iter().cloned().collect()
+    }
+
     // i: 1-based index
     pub fn modify(&mut self, mut i: usize, amount: &O::Update) {
         while i <= self.n {
@@ -28,7 +60,7 @@ impl<O: Operation> FenwickTree<O> {
         }
     }
 
-    pub fn query(&mut self, mut count: usize) -> O::Value {
+    pub fn query(&self, mut count: usize) -> O::Value {
         let mut result = O::i_value();
         while count > 0 {
             result = O::combine(&self.val[count], &result);
@@ -36,6 +68,71 @@ impl<O: Operation> FenwickTree<O> {
         }
         result
     }
+
+    // Largest count such that pred(query(count)) holds.
+    // pred must be monotone and hold for i_value().
+    pub fn lower_bound(&self, mut pred: impl FnMut(&O::Value) -> bool) -> usize {
+        let mut count = 0;
+        let mut acc = O::i_value();
+        let mut step = if self.n == 0 {
+            0
+        } else {
+            1 << (usize::BITS - 1 - self.n.leading_zeros())
+        };
+        while step > 0 {
+            if count + step <= self.n {
+                let next = O::combine(&acc, &self.val[count + step]);
+                if pred(&next) {
+                    count += step;
+                    acc = next;
+                }
+            }
+            step >>= 1;
+        }
+        count
+    }
+}
+
+impl<O: Invertible> FenwickTree<O> {
+    // Aggregate of the elements after the first l, up to the first r
+    pub fn query_range(&self, l: usize, r: usize) -> O::Value {
+        O::combine(&self.query(r), &O::inverse(&self.query(l)))
+    }
+
+    // i: 1-based index
+    pub fn get(&self, i: usize) -> O::Value {
+        self.query_range(i - 1, i)
+    }
+
+    // Inverse of from_iter() in O(n)
+    pub fn into_values(mut self) -> Vec<O::Value> {
+        for i in (1..=self.n).rev() {
+            let j = i + (i & i.wrapping_neg());
+            if j <= self.n {
+                self.val[j] = O::combine(&self.val[j], &O::inverse(&self.val[i]));
+            }
+        }
+        self.val.remove(0);
+        self.val
+    }
+}
+
+// Builds in O(n)
+impl<O: Operation> FromIterator<O::Value> for FenwickTree<O> {
+    fn from_iter<I: IntoIterator<Item = O::Value>>(iter: I) -> Self {
+        let data: Vec<O::Value> = iter.into_iter().collect();
+        let n = data.len();
+        // val[i] gathers the nodes below i in order, before element i is appended.
+        let mut val = vec![O::i_value(); n + 1];
+        for (i, v) in (1..=n).zip(data) {
+            val[i] = O::combine(&val[i], &v);
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                val[j] = O::combine(&val[j], &val[i]);
+            }
+        }
+        Self { val, n }
+    }
 }
 
 #[cfg(test)]
@@ -57,11 +154,17 @@ mod test {
         }
     }
 
+    impl Invertible for SumAdd {
+        fn inverse(v: &Self::Value) -> Self::Value {
+            -v
+        }
+    }
+
     #[test]
     fn modify() {
         let mut tree = FenwickTree::<SumAdd>::new(10);
         for (i, v) in [2, 1, 4, 3, 6, 5, 8, 7, 10, 9].iter().enumerate() {
-            tree.modify(i + 1, &v);
+            tree.modify(i + 1, v);
         }
         assert_eq!(vec![0, 2, 3, 4, 10, 6, 11, 8, 36, 10, 19], tree.val);
     }
@@ -70,9 +173,76 @@ mod test {
     fn query() {
         let mut tree = FenwickTree::<SumAdd>::new(10);
         for (i, v) in [2, 1, 4, 3, 6, 5, 8, 7, 10, 9].iter().enumerate() {
-            tree.modify(i + 1, &v);
+            tree.modify(i + 1, v);
         }
         assert_eq!(10, tree.query(4));
         assert_eq!(55, tree.query(10));
     }
+
+    #[test]
+    fn query_range() {
+        let mut tree = FenwickTree::<SumAdd>::new(10);
+        for (i, v) in [2, 1, 4, 3, 6, 5, 8, 7, 10, 9].iter().enumerate() {
+            tree.modify(i + 1, v);
+        }
+        assert_eq!(53, tree.query_range(1, 10));
+        assert_eq!(14, tree.query_range(3, 6));
+        assert_eq!(0, tree.query_range(4, 4));
+    }
+
+    #[test]
+    fn lower_bound() {
+        let data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let mut tree = FenwickTree::<SumAdd>::new(10);
+        for (i, v) in data.iter().enumerate() {
+            tree.modify(i + 1, v);
+        }
+        for k in 0..60 {
+            let expected = (0..=10)
+                .take_while(|&c| data[..c].iter().sum::<i64>() <= k)
+                .last()
+                .unwrap();
+            assert_eq!(expected, tree.lower_bound(|&v| v <= k));
+        }
+    }
+
+    #[test]
+    fn from_iter() {
+        let data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let tree: FenwickTree<SumAdd> = data.into_iter().collect();
+        assert_eq!(vec![0, 2, 3, 4, 10, 6, 11, 8, 36, 10, 19], tree.val);
+        assert_eq!(
+            vec![4, 3, 6],
+            (3..=5).map(|i| tree.get(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(data.to_vec(), tree.into_values());
+    }
+
+    struct Append;
+    impl Operation for Append {
+        type Value = Vec<usize>;
+        type Update = usize;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            [l.as_slice(), r.as_slice()].concat()
+        }
+        fn i_value() -> Self::Value {
+            vec![]
+        }
+        fn apply(v: &mut Self::Value, u: &Self::Update) {
+            v.push(*u);
+        }
+    }
+
+    #[test]
+    fn from_iter_order() {
+        for n in 0..20 {
+            let mut expected = FenwickTree::<Append>::new(n);
+            for i in 1..=n {
+                expected.modify(i, &i);
+            }
+            let tree: FenwickTree<Append> = (1..=n).map(|i| vec![i]).collect();
+            assert_eq!(expected.val, tree.val);
+            assert_eq!((1..=n).collect::<Vec<_>>(), tree.query(n));
+        }
+    }
 }
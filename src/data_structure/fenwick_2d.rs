@@ -0,0 +1,167 @@
+use crate::data_structure::fenwick::Operation;
+
+pub struct FenwickTree2D<O: Operation> {
+    val: Vec<O::Value>,
+    h: usize,
+    w: usize,
+}
+
+impl<O: Operation> FenwickTree2D<O> {
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            val: vec![O::i_value(); (h + 1) * (w + 1)],
+            h,
+            w,
+        }
+    }
+
+    // i, j: 1-based index
+    pub fn modify(&mut self, mut i: usize, j: usize, amount: &O::Update) {
+        while i <= self.h {
+            let mut j = j;
+            while j <= self.w {
+                O::apply(&mut self.val[i * (self.w + 1) + j], amount);
+                j = (j | (j - 1)) + 1;
+            }
+            i = (i | (i - 1)) + 1;
+        }
+    }
+
+    // Aggregate of the first `rows` rows and `cols` columns
+    pub fn query(&self, mut rows: usize, cols: usize) -> O::Value {
+        let mut result = O::i_value();
+        while rows > 0 {
+            let mut cols = cols;
+            while cols > 0 {
+                result = O::combine(&self.val[rows * (self.w + 1) + cols], &result);
+                cols &= cols - 1;
+            }
+            rows &= rows - 1;
+        }
+        result
+    }
+}
+
+// 2D Fenwick tree over points given in advance, using O(n log n) memory.
+pub struct OfflineFenwickTree2D<O: Operation> {
+    xs: Vec<i64>,
+    ys: Vec<Vec<i64>>,
+    val: Vec<Vec<O::Value>>,
+}
+
+impl<O: Operation> OfflineFenwickTree2D<O> {
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let mut xs: Vec<i64> = points.iter().map(|&(x, _)| x).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        let n = xs.len();
+        let mut ys = vec![vec![]; n + 1];
+        for &(x, y) in points {
+            let mut i = xs.binary_search(&x).unwrap() + 1;
+            while i <= n {
+                ys[i].push(y);
+                i = (i | (i - 1)) + 1;
+            }
+        }
+        for y in &mut ys {
+            y.sort_unstable();
+            y.dedup();
+        }
+        let val = ys.iter().map(|y| vec![O::i_value(); y.len() + 1]).collect();
+        Self { xs, ys, val }
+    }
+
+    // (x, y) must be one of the points given in new().
+    pub fn modify(&mut self, x: i64, y: i64, amount: &O::Update) {
+        let mut i = self.xs.binary_search(&x).unwrap() + 1;
+        while i <= self.xs.len() {
+            let ys = &self.ys[i];
+            let mut j = ys.binary_search(&y).unwrap() + 1;
+            while j <= ys.len() {
+                O::apply(&mut self.val[i][j], amount);
+                j = (j | (j - 1)) + 1;
+            }
+            i = (i | (i - 1)) + 1;
+        }
+    }
+
+    // Aggregate of points with x' < x and y' < y
+    pub fn query(&self, x: i64, y: i64) -> O::Value {
+        let mut result = O::i_value();
+        let mut i = self.xs.partition_point(|&px| px < x);
+        while i > 0 {
+            let mut j = self.ys[i].partition_point(|&py| py < y);
+            while j > 0 {
+                result = O::combine(&self.val[i][j], &result);
+                j &= j - 1;
+            }
+            i &= i - 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct SumAdd;
+    impl Operation for SumAdd {
+        type Value = i64;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            l + r
+        }
+        fn i_value() -> Self::Value {
+            0
+        }
+        fn apply(v: &mut Self::Value, u: &Self::Update) {
+            *v += *u;
+        }
+    }
+
+    const POINTS: [(i64, i64, i64); 7] = [
+        (1, 1, 3),
+        (3, 2, 7),
+        (2, 5, 5),
+        (5, 3, 1),
+        (3, 4, 9),
+        (5, 5, 2),
+        (3, 2, 4),
+    ];
+
+    fn brute(x: i64, y: i64) -> i64 {
+        POINTS
+            .iter()
+            .filter(|&&(px, py, _)| px < x && py < y)
+            .map(|&(_, _, v)| v)
+            .sum()
+    }
+
+    #[test]
+    fn query() {
+        let mut tree = FenwickTree2D::<SumAdd>::new(5, 5);
+        for &(x, y, v) in &POINTS {
+            tree.modify(x as usize, y as usize, &v);
+        }
+        for x in 0..=5 {
+            for y in 0..=5 {
+                assert_eq!(brute(x + 1, y + 1), tree.query(x as usize, y as usize));
+            }
+        }
+    }
+
+    #[test]
+    fn offline_query() {
+        let points: Vec<(i64, i64)> = POINTS.iter().map(|&(x, y, _)| (x, y)).collect();
+        let mut tree = OfflineFenwickTree2D::<SumAdd>::new(&points);
+        for &(x, y, v) in &POINTS {
+            tree.modify(x, y, &v);
+        }
+        for x in 0..=7 {
+            for y in 0..=7 {
+                assert_eq!(brute(x, y), tree.query(x, y));
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::data_structure::fenwick::{FenwickTree, Operation};
+
+struct Count;
+impl Operation for Count {
+    type Value = usize;
+    type Update = isize;
+    fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+        l + r
+    }
+    fn i_value() -> Self::Value {
+        0
+    }
+    fn apply(v: &mut Self::Value, u: &Self::Update) {
+        *v = v.wrapping_add_signed(*u);
+    }
+}
+
+// Multiset of integers in [0, n) with order statistics
+pub struct FenwickMultiset {
+    tree: FenwickTree<Count>,
+    n: usize,
+    len: usize,
+}
+
+impl FenwickMultiset {
+    pub fn new(n: usize) -> Self {
+        Self {
+            tree: FenwickTree::new(n),
+            n,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, x: usize) {
+        assert!(x < self.n);
+        self.tree.modify(x + 1, &1);
+        self.len += 1;
+    }
+
+    // Returns if x was in the set.
+    pub fn erase(&mut self, x: usize) -> bool {
+        if self.count(x) == 0 {
+            false
+        } else {
+            self.tree.modify(x + 1, &-1);
+            self.len -= 1;
+            true
+        }
+    }
+
+    pub fn count(&self, x: usize) -> usize {
+        assert!(x < self.n);
+        self.rank(x + 1) - self.rank(x)
+    }
+
+    // Number of elements less than x
+    pub fn rank(&self, x: usize) -> usize {
+        self.tree.query(x)
+    }
+
+    // k: 0-based
+    pub fn kth(&self, k: usize) -> Option<usize> {
+        if k < self.len {
+            Some(self.tree.lower_bound(|&count| count <= k))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn kth() {
+        let mut set = FenwickMultiset::new(10);
+        for x in [3, 7, 3, 0, 9, 5] {
+            set.insert(x);
+        }
+        assert_eq!(
+            vec![Some(0), Some(3), Some(3), Some(5), Some(7), Some(9), None],
+            (0..7).map(|k| set.kth(k)).collect::<Vec<_>>()
+        );
+        assert!(set.erase(3));
+        assert!(!set.erase(4));
+        assert_eq!(5, set.len());
+        assert_eq!(Some(5), set.kth(2));
+    }
+
+    #[test]
+    fn rank() {
+        let mut set = FenwickMultiset::new(10);
+        for x in [3, 7, 3, 0, 9, 5] {
+            set.insert(x);
+        }
+        assert_eq!(
+            vec![0, 1, 1, 1, 3, 3, 4, 4, 5, 5, 6],
+            (0..=10).map(|x| set.rank(x)).collect::<Vec<_>>()
+        );
+        assert_eq!(2, set.count(3));
+        assert_eq!(0, set.count(4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_range() {
+        let mut set = FenwickMultiset::new(3);
+        set.insert(5);
+    }
+}
@@ -13,11 +13,11 @@ impl<T> Graph<T> {
 
     pub fn connect(&mut self, from: usize, to: usize, data: T) {
         let new_edge = self.edges.len();
-        let prev = std::mem::replace(&mut self.nodes[from], Some(new_edge));
+        let prev = self.nodes[from].replace(new_edge);
         self.edges.push((prev, to, data));
     }
 
-    pub fn neighbors(&self, node: usize) -> Neighbors<T> {
+    pub fn neighbors(&self, node: usize) -> Neighbors<'_, T> {
         Neighbors {
             graph: self,
             next: self.nodes[node],
@@ -31,6 +31,23 @@ impl<T> Graph<T> {
     pub fn edge_count(&self) -> usize {
         self.edges.len()
     }
+
+    // (from, to, data) of every edge, indexed in the order of connection
+    pub fn edges(&self) -> Vec<(usize, usize, &T)> {
+        let mut from = vec![0; self.edges.len()];
+        for (node, &head) in self.nodes.iter().enumerate() {
+            let mut next = head;
+            while let Some(edge) = next {
+                from[edge] = node;
+                next = self.edges[edge].0;
+            }
+        }
+        self.edges
+            .iter()
+            .zip(from)
+            .map(|((_, to, data), from)| (from, *to, data))
+            .collect()
+    }
 }
 
 pub struct Neighbors<'a, T> {
@@ -78,4 +95,16 @@ mod test {
             graph.neighbors(1).collect::<Vec<(usize, &())>>()
         );
     }
+
+    #[test]
+    fn edges() {
+        let mut graph = Graph::new(3, 3);
+        graph.connect(1, 2, 'a');
+        graph.connect(0, 1, 'b');
+        graph.connect(1, 0, 'c');
+        assert_eq!(
+            vec![(1, 2, &'a'), (0, 1, &'b'), (1, 0, &'c')],
+            graph.edges()
+        );
+    }
 }
@@ -77,7 +77,7 @@ impl HeavyLight {
         self.pos[node]
     }
 
-    pub fn path(&self, u: usize, v: usize) -> Path {
+    pub fn path(&self, u: usize, v: usize) -> Path<'_> {
         Path {
             hld: self,
             endpoints: Some((u, v)),
@@ -1,3 +1,5 @@
+use crate::math::{Action, Monoid};
+
 pub trait Operation {
     type Value: Clone;
     type Update: Clone;
@@ -10,6 +12,30 @@ pub trait Operation {
     fn apply(v: &mut Self::Value, len: usize, u: &Self::Update);
 }
 
+impl<S: Action> Operation for S {
+    type Value = <S::M as Monoid>::T;
+    type Update = <S::A as Monoid>::T;
+
+    fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+        S::M::apply(l, r)
+    }
+    fn i_value() -> Self::Value {
+        S::M::ident()
+    }
+    fn compose(a: &mut Self::Update, b: &Self::Update) {
+        *a = S::A::apply(a, b);
+    }
+    fn is_i(u: &Self::Update) -> bool {
+        *u == S::A::ident()
+    }
+    fn i_update() -> Self::Update {
+        S::A::ident()
+    }
+    fn apply(v: &mut Self::Value, len: usize, u: &Self::Update) {
+        S::act(v, len, u);
+    }
+}
+
 pub struct LazySegmentTree<O: Operation> {
     val: Vec<O::Value>,
     lazy: Vec<O::Update>,
@@ -48,15 +74,19 @@ impl<O: Operation> LazySegmentTree<O> {
         }
     }
 
+    // len: length of each child of i
+    fn push(&mut self, i: usize, len: usize) {
+        if !O::is_i(&self.lazy[i]) {
+            let amount = std::mem::replace(&mut self.lazy[i], O::i_update());
+            self.apply(i << 1, len, &amount);
+            self.apply(i << 1 | 1, len, &amount);
+        }
+    }
+
     fn propagate(&mut self, i: usize) {
         let mut len = 1 << (self.h - 1);
         for shift in (1..=self.h).rev() {
-            let ptr = i >> shift;
-            if !O::is_i(&self.lazy[ptr]) {
-                self.apply(ptr << 1, len, &self.lazy[ptr].clone());
-                self.apply(ptr << 1 | 1, len, &self.lazy[ptr].clone());
-                self.lazy[ptr] = O::i_update();
-            }
+            self.push(i >> shift, len);
             len >>= 1;
         }
     }
@@ -90,27 +120,146 @@ impl<O: Operation> LazySegmentTree<O> {
         }
     }
 
-    pub fn query(&mut self, l: usize, r: usize) -> O::Value {
+    // pending[k]: composition of the updates pending above the ancestor of
+    // leaf i at height k, oldest first.
+    fn pending(&self, i: usize) -> Vec<O::Update> {
+        let h = self.h as usize;
+        let mut pending = vec![O::i_update(); h + 1];
+        for k in (0..h).rev() {
+            let mut amount = self.lazy[i >> (k + 1)].clone();
+            O::compose(&mut amount, &pending[k + 1]);
+            pending[k] = amount;
+        }
+        pending
+    }
+
+    // Pending updates are applied to the visited nodes on the fly.
+    pub fn query(&self, l: usize, r: usize) -> O::Value {
+        if l >= r {
+            return O::i_value();
+        }
         let mut lp = l + self.n;
         let mut rp = r + self.n;
-        self.propagate(lp);
-        if l < r {
-            self.propagate(rp - 1);
-        }
-        let mut result = O::i_value();
+        let l_pending = self.pending(lp);
+        let r_pending = self.pending(rp - 1);
+        let mut left = O::i_value();
+        let mut right = O::i_value();
+        let mut k = 0;
         while lp < rp {
             if lp & 1 == 1 {
-                result = O::combine(&result, &self.val[lp]);
+                left = O::combine(&left, &self.value(lp, 1 << k, &l_pending[k]));
                 lp += 1;
             }
             if rp & 1 == 1 {
                 rp -= 1;
-                result = O::combine(&self.val[rp], &result);
+                right = O::combine(&self.value(rp, 1 << k, &r_pending[k]), &right);
             }
             lp >>= 1;
             rp >>= 1;
+            k += 1;
         }
-        result
+        O::combine(&left, &right)
+    }
+
+    // Value of node i with the updates pending above it applied
+    fn value(&self, i: usize, len: usize, pending: &O::Update) -> O::Value {
+        let mut v = self.val[i].clone();
+        if !O::is_i(pending) {
+            O::apply(&mut v, len, pending);
+        }
+        v
+    }
+
+    // Updates pending above the children of i
+    fn pending_below(&self, i: usize, pending: &O::Update) -> O::Update {
+        let mut amount = self.lazy[i].clone();
+        O::compose(&mut amount, pending);
+        amount
+    }
+
+    // Nodes covering [l, r) from left to right, with their lengths and the
+    // updates pending above them. l must be less than r.
+    fn nodes(&self, l: usize, r: usize) -> Vec<(usize, usize, O::Update)> {
+        let mut l = l + self.n;
+        let mut r = r + self.n;
+        let l_pending = self.pending(l);
+        let r_pending = self.pending(r - 1);
+        let mut left = vec![];
+        let mut right = vec![];
+        let mut k = 0;
+        while l < r {
+            if l & 1 == 1 {
+                left.push((l, 1 << k, l_pending[k].clone()));
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right.push((r, 1 << k, r_pending[k].clone()));
+            }
+            l >>= 1;
+            r >>= 1;
+            k += 1;
+        }
+        left.extend(right.into_iter().rev());
+        left
+    }
+
+    // Largest r such that pred(query(l, r)) holds.
+    // pred must be monotone and hold for i_value().
+    pub fn max_right(&self, l: usize, mut pred: impl FnMut(&O::Value) -> bool) -> usize {
+        if l == self.n {
+            return self.n;
+        }
+        let mut acc = O::i_value();
+        for (node, mut len, mut pending) in self.nodes(l, self.n) {
+            let next = O::combine(&acc, &self.value(node, len, &pending));
+            if pred(&next) {
+                acc = next;
+                continue;
+            }
+            let mut i = node;
+            while i < self.n {
+                pending = self.pending_below(i, &pending);
+                len >>= 1;
+                i <<= 1;
+                let next = O::combine(&acc, &self.value(i, len, &pending));
+                if pred(&next) {
+                    acc = next;
+                    i += 1;
+                }
+            }
+            return i - self.n;
+        }
+        self.n
+    }
+
+    // Smallest l such that pred(query(l, r)) holds.
+    // pred must be monotone and hold for i_value().
+    pub fn min_left(&self, r: usize, mut pred: impl FnMut(&O::Value) -> bool) -> usize {
+        if r == 0 {
+            return 0;
+        }
+        let mut acc = O::i_value();
+        for (node, mut len, mut pending) in self.nodes(0, r).into_iter().rev() {
+            let next = O::combine(&self.value(node, len, &pending), &acc);
+            if pred(&next) {
+                acc = next;
+                continue;
+            }
+            let mut i = node;
+            while i < self.n {
+                pending = self.pending_below(i, &pending);
+                len >>= 1;
+                i = i << 1 | 1;
+                let next = O::combine(&self.value(i, len, &pending), &acc);
+                if pred(&next) {
+                    acc = next;
+                    i -= 1;
+                }
+            }
+            return i + 1 - self.n;
+        }
+        0
     }
 }
 
@@ -144,7 +293,7 @@ mod test {
 
     #[test]
     fn query() {
-        let mut tree = LazySegmentTree::<SumAdd>::new(10, [2, 1, 4, 3, 6, 5, 8, 7, 10, 9]);
+        let tree = LazySegmentTree::<SumAdd>::new(10, [2, 1, 4, 3, 6, 5, 8, 7, 10, 9]);
         assert_eq!(10, tree.query(0, 4));
         assert_eq!(48, tree.query(3, 10));
     }
@@ -159,4 +308,136 @@ mod test {
         );
         assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 0, -5, 0], tree.lazy);
     }
+
+    #[test]
+    fn max_right() {
+        let mut data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let mut tree = LazySegmentTree::<SumAdd>::new(10, data);
+        tree.modify(2, 7, &3);
+        data[2..7].iter_mut().for_each(|v| *v += 3);
+        tree.modify(4, 10, &-1);
+        data[4..10].iter_mut().for_each(|v| *v -= 1);
+        for l in 0..=10 {
+            for k in 0..70 {
+                let expected = (l..=10)
+                    .take_while(|&r| data[l..r].iter().sum::<i64>() <= k)
+                    .last()
+                    .unwrap();
+                assert_eq!(expected, tree.max_right(l, |&v| v <= k));
+            }
+        }
+    }
+
+    #[test]
+    fn min_left() {
+        let mut data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let mut tree = LazySegmentTree::<SumAdd>::new(10, data);
+        tree.modify(2, 7, &3);
+        data[2..7].iter_mut().for_each(|v| *v += 3);
+        tree.modify(4, 10, &-1);
+        data[4..10].iter_mut().for_each(|v| *v -= 1);
+        for r in 0..=10 {
+            for k in 0..70 {
+                let expected = (0..=r)
+                    .rev()
+                    .take_while(|&l| data[l..r].iter().sum::<i64>() <= k)
+                    .last()
+                    .unwrap();
+                assert_eq!(expected, tree.min_left(r, |&v| v <= k));
+            }
+        }
+    }
+
+    #[test]
+    fn query_pending() {
+        let mut data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let mut tree = LazySegmentTree::<SumAdd>::new(10, data);
+        for (l, r, v) in [(0, 10, 2), (3, 8, -1), (5, 6, 4), (1, 9, 3), (6, 10, -2)] {
+            tree.modify(l, r, &v);
+            data[l..r].iter_mut().for_each(|x| *x += v);
+        }
+        let tree = &tree;
+        std::thread::scope(|s| {
+            for l in 0..=10 {
+                s.spawn(move || {
+                    for r in l..=10 {
+                        assert_eq!(data[l..r].iter().sum::<i64>(), tree.query(l, r));
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn descent_shared() {
+        let mut data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9, 1];
+        let mut tree = LazySegmentTree::<SumAdd>::new(11, data);
+        for (l, r, v) in [(0, 11, 2), (3, 8, -1), (5, 6, 4), (1, 9, 3), (6, 11, -2)] {
+            tree.modify(l, r, &v);
+            data[l..r].iter_mut().for_each(|x| *x += v);
+        }
+        let tree = std::sync::Arc::new(tree);
+        std::thread::scope(|s| {
+            for k in (0..80).step_by(5) {
+                let tree = std::sync::Arc::clone(&tree);
+                s.spawn(move || {
+                    for i in 0..=11 {
+                        let expected = (i..=11)
+                            .take_while(|&r| data[i..r].iter().sum::<i64>() <= k)
+                            .last()
+                            .unwrap();
+                        assert_eq!(expected, tree.max_right(i, |&v| v <= k));
+                        let expected = (0..=i)
+                            .rev()
+                            .take_while(|&l| data[l..i].iter().sum::<i64>() <= k)
+                            .last()
+                            .unwrap();
+                        assert_eq!(expected, tree.min_left(i, |&v| v <= k));
+                    }
+                });
+            }
+        });
+    }
+
+    struct ConcatAdd;
+    impl Operation for ConcatAdd {
+        type Value = Vec<i64>;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            [l.as_slice(), r.as_slice()].concat()
+        }
+        fn i_value() -> Self::Value {
+            vec![]
+        }
+        fn compose(a: &mut Self::Update, b: &Self::Update) {
+            *a += b
+        }
+        fn is_i(u: &Self::Update) -> bool {
+            *u == 0
+        }
+        fn i_update() -> Self::Update {
+            0
+        }
+        fn apply(v: &mut Self::Value, _len: usize, u: &Self::Update) {
+            v.iter_mut().for_each(|x| *x += u);
+        }
+    }
+
+    #[test]
+    fn query_order() {
+        for n in [8, 11] {
+            let mut data: Vec<i64> = (0..n).collect();
+            let mut tree =
+                LazySegmentTree::<ConcatAdd>::new(n as usize, data.iter().map(|&v| vec![v]));
+            for (l, r, v) in [(0, 5, 10), (2, 7, 100), (6, 8, 1000)] {
+                tree.modify(l, r, &v);
+                data[l..r].iter_mut().for_each(|x| *x += v);
+            }
+            for l in 0..=data.len() {
+                for r in l..=data.len() {
+                    assert_eq!(data[l..r], tree.query(l, r));
+                }
+            }
+        }
+    }
 }
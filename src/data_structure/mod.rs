@@ -1,13 +1,39 @@
+pub mod concurrent_disjoint_set;
 pub mod disjoint_set;
+pub mod dynamic_lazy_segtree;
+pub mod dynamic_segtree;
 pub mod fenwick;
+pub mod fenwick_2d;
+pub mod fenwick_multiset;
 pub mod graph;
 pub mod hld;
 pub mod lazy_segtree;
+pub mod persistent_disjoint_set;
+pub mod persistent_segtree;
+pub mod range_fenwick;
+pub mod rollback_disjoint_set;
 pub mod segtree;
+pub mod segtree_2d;
+pub mod segtree_beats;
+pub mod sparse_fenwick;
+pub mod weighted_disjoint_set;
 
-pub use disjoint_set::SetForest;
+pub use concurrent_disjoint_set::ConcurrentSetForest;
+pub use disjoint_set::{AggregateSetForest, SetForest};
+pub use dynamic_lazy_segtree::DynamicLazySegmentTree;
+pub use dynamic_segtree::DynamicSegmentTree;
 pub use fenwick::FenwickTree;
+pub use fenwick_2d::{FenwickTree2D, OfflineFenwickTree2D};
+pub use fenwick_multiset::FenwickMultiset;
 pub use graph::Graph;
 pub use hld::HeavyLight;
 pub use lazy_segtree::LazySegmentTree;
+pub use persistent_disjoint_set::PersistentSetForest;
+pub use persistent_segtree::PersistentSegmentTree;
+pub use range_fenwick::RangeFenwickTree;
+pub use rollback_disjoint_set::{OfflineConnectivity, RollbackSetForest};
 pub use segtree::SegmentTree;
+pub use segtree_2d::{OfflineSegmentTree2D, SegmentTree2D};
+pub use segtree_beats::SegmentTreeBeats;
+pub use sparse_fenwick::SparseFenwickTree;
+pub use weighted_disjoint_set::WeightedSetForest;
@@ -0,0 +1,122 @@
+// Disjoint set forest remembering when each item was attached, so that the
+// sets at any past time can be inspected. Time t is after the first t joins.
+pub struct PersistentSetForest {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    since: Vec<usize>,
+    time: usize,
+}
+
+impl PersistentSetForest {
+    pub fn new(s: usize) -> Self {
+        Self {
+            parent: (0..s).collect(),
+            size: vec![1; s],
+            since: vec![usize::MAX; s],
+            time: 0,
+        }
+    }
+
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    // Root of the set containing i at time t
+    pub fn find(&self, mut i: usize, t: usize) -> usize {
+        while self.since[i] <= t {
+            i = self.parent[i];
+        }
+        i
+    }
+
+    // Returns if two items were in the same set.
+    pub fn join(&mut self, u: usize, v: usize) -> bool {
+        self.time += 1;
+        let mut pu = self.find(u, self.time);
+        let mut pv = self.find(v, self.time);
+        if pu == pv {
+            true
+        } else {
+            if self.size[pu] < self.size[pv] {
+                std::mem::swap(&mut pu, &mut pv);
+            }
+            self.parent[pv] = pu;
+            self.size[pu] += self.size[pv];
+            self.since[pv] = self.time;
+            false
+        }
+    }
+
+    pub fn same(&self, u: usize, v: usize, t: usize) -> bool {
+        self.find(u, t) == self.find(v, t)
+    }
+
+    // Earliest time two items were in the same set
+    pub fn joined_at(&self, mut u: usize, mut v: usize) -> Option<usize> {
+        if !self.same(u, v, self.time) {
+            return None;
+        }
+        let mut t = 0;
+        while u != v {
+            if self.since[u] < self.since[v] {
+                t = t.max(self.since[u]);
+                u = self.parent[u];
+            } else {
+                t = t.max(self.since[v]);
+                v = self.parent[v];
+            }
+        }
+        Some(t)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_structure::SetForest;
+
+    const JOINS: [(usize, usize); 8] = [
+        (0, 1),
+        (2, 3),
+        (4, 5),
+        (1, 3),
+        (0, 2),
+        (6, 7),
+        (5, 7),
+        (3, 6),
+    ];
+
+    #[test]
+    fn same() {
+        let mut forest = PersistentSetForest::new(9);
+        for &(u, v) in &JOINS {
+            forest.join(u, v);
+        }
+        let mut expected = SetForest::new(9);
+        for t in 0..=JOINS.len() {
+            if t > 0 {
+                expected.join(JOINS[t - 1].0, JOINS[t - 1].1);
+            }
+            for u in 0..9 {
+                for v in 0..9 {
+                    assert_eq!(expected.same(u, v), forest.same(u, v, t));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn joined_at() {
+        let mut forest = PersistentSetForest::new(9);
+        for &(u, v) in &JOINS {
+            forest.join(u, v);
+        }
+        assert_eq!(Some(0), forest.joined_at(4, 4));
+        assert_eq!(Some(1), forest.joined_at(1, 0));
+        assert_eq!(Some(4), forest.joined_at(0, 3));
+        assert_eq!(Some(4), forest.joined_at(2, 1));
+        assert_eq!(Some(8), forest.joined_at(0, 7));
+        assert_eq!(Some(7), forest.joined_at(4, 6));
+        assert_eq!(None, forest.joined_at(8, 0));
+    }
+}
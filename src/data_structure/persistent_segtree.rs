@@ -0,0 +1,249 @@
+use crate::data_structure::segtree::Operation;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Version(u32);
+
+pub struct PersistentSegmentTree<O: Operation> {
+    val: Vec<O::Value>,
+    child: Vec<[u32; 2]>,
+    n: usize,
+}
+
+impl<O: Operation> PersistentSegmentTree<O> {
+    // Node 0 is the identity-filled tree, whose children are itself.
+    pub fn new(n: usize) -> Self {
+        Self {
+            val: vec![O::i_value()],
+            child: vec![[0, 0]],
+            n,
+        }
+    }
+
+    fn node(&mut self, val: O::Value, child: [u32; 2]) -> u32 {
+        self.val.push(val);
+        self.child.push(child);
+        (self.val.len() - 1) as u32
+    }
+
+    // Version whose every element is i_value()
+    pub fn empty(&self) -> Version {
+        Version(0)
+    }
+
+    pub fn build(&mut self, data: impl IntoIterator<Item = O::Value>) -> Version {
+        if self.n == 0 {
+            return Version(0);
+        }
+        let mut data: Vec<O::Value> = data.into_iter().take(self.n).collect();
+        data.resize(self.n, O::i_value());
+        Version(self.build_at(&data))
+    }
+
+    fn build_at(&mut self, data: &[O::Value]) -> u32 {
+        if data.len() == 1 {
+            return self.node(data[0].clone(), [0, 0]);
+        }
+        let (l, r) = data.split_at(data.len() / 2);
+        let l = self.build_at(l);
+        let r = self.build_at(r);
+        let val = O::combine(&self.val[l as usize], &self.val[r as usize]);
+        self.node(val, [l, r])
+    }
+
+    // Returns the new version; `version` stays intact.
+    pub fn modify(&mut self, version: Version, i: usize, amount: &O::Update) -> Version {
+        assert!(i < self.n);
+        Version(self.modify_at(version.0, 0, self.n, i, amount))
+    }
+
+    fn modify_at(&mut self, node: u32, s: usize, e: usize, i: usize, amount: &O::Update) -> u32 {
+        if e - s == 1 {
+            let mut val = self.val[node as usize].clone();
+            O::apply(&mut val, amount);
+            return self.node(val, [0, 0]);
+        }
+        let m = (s + e) / 2;
+        let [mut l, mut r] = self.child[node as usize];
+        if i < m {
+            l = self.modify_at(l, s, m, i, amount);
+        } else {
+            r = self.modify_at(r, m, e, i, amount);
+        }
+        let val = O::combine(&self.val[l as usize], &self.val[r as usize]);
+        self.node(val, [l, r])
+    }
+
+    pub fn query(&self, version: Version, l: usize, r: usize) -> O::Value {
+        self.query_at(version.0, 0, self.n, l, r)
+    }
+
+    fn query_at(&self, node: u32, s: usize, e: usize, l: usize, r: usize) -> O::Value {
+        if r <= s || e <= l {
+            O::i_value()
+        } else if l <= s && e <= r {
+            self.val[node as usize].clone()
+        } else {
+            let m = (s + e) / 2;
+            let [lc, rc] = self.child[node as usize];
+            O::combine(
+                &self.query_at(lc, s, m, l, r),
+                &self.query_at(rc, m, e, l, r),
+            )
+        }
+    }
+
+    // Largest r such that pred(query(version, l, r)) holds.
+    // pred must be monotone and hold for i_value().
+    pub fn max_right(
+        &self,
+        version: Version,
+        l: usize,
+        mut pred: impl FnMut(&O::Value) -> bool,
+    ) -> usize {
+        let mut acc = O::i_value();
+        let value = |node: u32, _| self.val[node as usize].clone();
+        self.max_right_at((version.0, 0), 0, self.n, l, &mut acc, &value, &mut pred)
+            .unwrap_or(self.n)
+    }
+
+    // max_right over the difference of two versions, where sub(a, b) inverts
+    // the changes made between `lo` and `hi`.
+    // e.g. k-th smallest in a subarray, with counting versions of its prefixes.
+    pub fn max_right_diff(
+        &self,
+        hi: Version,
+        lo: Version,
+        l: usize,
+        sub: impl Fn(&O::Value, &O::Value) -> O::Value,
+        mut pred: impl FnMut(&O::Value) -> bool,
+    ) -> usize {
+        let mut acc = O::i_value();
+        let value = |hi: u32, lo: u32| sub(&self.val[hi as usize], &self.val[lo as usize]);
+        self.max_right_at((hi.0, lo.0), 0, self.n, l, &mut acc, &value, &mut pred)
+            .unwrap_or(self.n)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn max_right_at(
+        &self,
+        (hi, lo): (u32, u32),
+        s: usize,
+        e: usize,
+        l: usize,
+        acc: &mut O::Value,
+        value: &impl Fn(u32, u32) -> O::Value,
+        pred: &mut impl FnMut(&O::Value) -> bool,
+    ) -> Option<usize> {
+        if e <= l {
+            return None;
+        }
+        if l <= s {
+            let next = O::combine(acc, &value(hi, lo));
+            if pred(&next) {
+                *acc = next;
+                return None;
+            }
+            if e - s == 1 {
+                return Some(s);
+            }
+        }
+        let m = (s + e) / 2;
+        let [hl, hr] = self.child[hi as usize];
+        let [ll, lr] = self.child[lo as usize];
+        self.max_right_at((hl, ll), s, m, l, acc, value, pred)
+            .or_else(|| self.max_right_at((hr, lr), m, e, l, acc, value, pred))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct SumAdd;
+    impl Operation for SumAdd {
+        type Value = i64;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            l + r
+        }
+        fn i_value() -> Self::Value {
+            0
+        }
+        fn apply(v: &mut Self::Value, u: &Self::Update) {
+            *v += *u;
+        }
+    }
+
+    #[test]
+    fn modify() {
+        let mut tree = PersistentSegmentTree::<SumAdd>::new(10);
+        let v0 = tree.build([2, 1, 4, 3, 6, 5, 8, 7, 10, 9]);
+        let v1 = tree.modify(v0, 5, &-3);
+        let v2 = tree.modify(v1, 0, &10);
+        let v3 = tree.modify(v0, 9, &1);
+        assert_eq!(55, tree.query(v0, 0, 10));
+        assert_eq!(52, tree.query(v1, 0, 10));
+        assert_eq!(62, tree.query(v2, 0, 10));
+        assert_eq!(56, tree.query(v3, 0, 10));
+        assert_eq!(14, tree.query(v0, 3, 6));
+        assert_eq!(11, tree.query(v1, 3, 6));
+        assert_eq!(13, tree.query(v2, 0, 2));
+        assert_eq!(0, tree.query(tree.empty(), 0, 10));
+    }
+
+    #[test]
+    fn kth_smallest() {
+        let data = [5, 1, 4, 3, 1, 0, 2, 5, 3, 4];
+        let mut tree = PersistentSegmentTree::<SumAdd>::new(6);
+        let mut versions = vec![tree.empty()];
+        for &x in &data {
+            let last = *versions.last().unwrap();
+            versions.push(tree.modify(last, x, &1));
+        }
+        for l in 0..data.len() {
+            for r in l + 1..=data.len() {
+                let mut sorted = data[l..r].to_vec();
+                sorted.sort_unstable();
+                for (k, &expected) in sorted.iter().enumerate() {
+                    let found = tree.max_right_diff(
+                        versions[r],
+                        versions[l],
+                        0,
+                        |a, b| a - b,
+                        |&count| count <= k as i64,
+                    );
+                    assert_eq!(expected, found);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_right() {
+        let mut tree = PersistentSegmentTree::<SumAdd>::new(10);
+        let mut data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let v0 = tree.build(data);
+        let v1 = tree.modify(v0, 4, &-4);
+        for (version, delta) in [(v0, 0), (v1, -4)] {
+            data[4] = 6 + delta;
+            for l in 0..=10 {
+                for k in 0..60 {
+                    let expected = (l..=10)
+                        .take_while(|&r| data[l..r].iter().sum::<i64>() <= k)
+                        .last()
+                        .unwrap();
+                    assert_eq!(expected, tree.max_right(version, l, |&v| v <= k));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let mut tree = PersistentSegmentTree::<SumAdd>::new(0);
+        let v0 = tree.build([]);
+        assert_eq!(tree.empty(), v0);
+        assert_eq!(0, tree.query(v0, 0, 0));
+        assert_eq!(0, tree.max_right(v0, 0, |_| false));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::data_structure::FenwickTree;
+use crate::math::{power, CommutativeMonoid, Group};
+
+// Range update and range query, with the differences d of the elements kept
+// in two Fenwick trees: sum of the first p elements = p * sum(d[j]) - sum(j * d[j]).
+// n * v is computed by fast exponentiation on G.
+pub struct RangeFenwickTree<G: CommutativeMonoid + Group> {
+    diff: FenwickTree<G>,
+    weighted: FenwickTree<G>,
+}
+
+impl<G: CommutativeMonoid + Group> RangeFenwickTree<G> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            diff: FenwickTree::new(n),
+            weighted: FenwickTree::new(n),
+        }
+    }
+
+    // Applies amount to the elements after the first l, up to the first r
+    pub fn modify(&mut self, l: usize, r: usize, amount: &G::T) {
+        let inverse = G::inverse(amount);
+        self.diff.modify(l + 1, amount);
+        self.diff.modify(r + 1, &inverse);
+        self.weighted.modify(l + 1, &power::<G>(amount, l));
+        self.weighted.modify(r + 1, &power::<G>(&inverse, r));
+    }
+
+    // i: 0-based index
+    pub fn get(&self, i: usize) -> G::T {
+        self.diff.query(i + 1)
+    }
+
+    pub fn query(&self, count: usize) -> G::T {
+        G::apply(
+            &power::<G>(&self.diff.query(count), count),
+            &G::inverse(&self.weighted.query(count)),
+        )
+    }
+
+    pub fn query_range(&self, l: usize, r: usize) -> G::T {
+        G::apply(&self.query(r), &G::inverse(&self.query(l)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::Monoid;
+
+    struct Sum;
+    impl Monoid for Sum {
+        type T = i64;
+        fn apply(l: &i64, r: &i64) -> i64 {
+            l + r
+        }
+        fn ident() -> i64 {
+            0
+        }
+    }
+    impl CommutativeMonoid for Sum {}
+    impl Group for Sum {
+        fn inverse(a: &i64) -> i64 {
+            -a
+        }
+    }
+
+    #[test]
+    fn query_range() {
+        let mut data = [0; 10];
+        let mut tree = RangeFenwickTree::<Sum>::new(10);
+        for (l, r, v) in [(0, 10, 2), (3, 8, -1), (5, 6, 4), (1, 9, 3), (6, 10, -2)] {
+            tree.modify(l, r, &v);
+            data[l..r].iter_mut().for_each(|x| *x += v);
+        }
+        for l in 0..=10 {
+            for r in l..=10 {
+                assert_eq!(data[l..r].iter().sum::<i64>(), tree.query_range(l, r));
+            }
+        }
+    }
+
+    #[test]
+    fn get() {
+        let mut tree = RangeFenwickTree::<Sum>::new(5);
+        tree.modify(1, 4, &3);
+        tree.modify(0, 2, &-1);
+        tree.modify(3, 5, &5);
+        assert_eq!(
+            vec![-1, 2, 3, 8, 5],
+            (0..5).map(|i| tree.get(i)).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+// Disjoint set forest without path compression, so that joins can be undone.
+pub struct RollbackSetForest {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    // Root attached by each join, None if the items were already joined
+    history: Vec<Option<usize>>,
+    count: usize,
+}
+
+impl RollbackSetForest {
+    pub fn new(s: usize) -> Self {
+        Self {
+            parent: (0..s).collect(),
+            size: vec![1; s],
+            history: vec![],
+            count: s,
+        }
+    }
+
+    pub fn find(&self, mut i: usize) -> usize {
+        while i != self.parent[i] {
+            i = self.parent[i];
+        }
+        i
+    }
+
+    // Returns if two items were in the same set.
+    pub fn join(&mut self, u: usize, v: usize) -> bool {
+        let mut pu = self.find(u);
+        let mut pv = self.find(v);
+        if pu == pv {
+            self.history.push(None);
+            true
+        } else {
+            if self.size[pu] < self.size[pv] {
+                std::mem::swap(&mut pu, &mut pv);
+            }
+            self.parent[pv] = pu;
+            self.size[pu] += self.size[pv];
+            self.count -= 1;
+            self.history.push(Some(pv));
+            false
+        }
+    }
+
+    pub fn same(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    pub fn size(&self, i: usize) -> usize {
+        self.size[self.find(i)]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    // Undoes every join made after snapshot() returned `to`.
+    pub fn rollback(&mut self, to: usize) {
+        while self.history.len() > to {
+            if let Some(v) = self.history.pop().unwrap() {
+                let u = self.parent[v];
+                self.parent[v] = v;
+                self.size[u] -= self.size[v];
+                self.count += 1;
+            }
+        }
+    }
+}
+
+fn insert(
+    edges: &mut [Vec<(usize, usize)>],
+    i: usize,
+    s: usize,
+    e: usize,
+    (l, r): (usize, usize),
+    edge: (usize, usize),
+) {
+    if r <= s || e <= l {
+        return;
+    }
+    if l <= s && e <= r {
+        edges[i].push(edge);
+        return;
+    }
+    let m = (s + e) / 2;
+    insert(edges, i << 1, s, m, (l, r), edge);
+    insert(edges, i << 1 | 1, m, e, (l, r), edge);
+}
+
+enum Event {
+    Add,
+    Remove,
+    Query(usize, usize),
+}
+
+// Connectivity queries over a timeline of edge additions and removals,
+// answered by divide and conquer over time.
+pub struct OfflineConnectivity {
+    n: usize,
+    events: Vec<Event>,
+    // Edge and the time span [begin, end) it exists in
+    spans: Vec<(usize, usize, usize, usize)>,
+    open: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl OfflineConnectivity {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            events: vec![],
+            spans: vec![],
+            open: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, u: usize, v: usize) {
+        let key = (u.min(v), u.max(v));
+        self.open.entry(key).or_default().push(self.events.len());
+        self.events.push(Event::Add);
+    }
+
+    // Removes an edge added before.
+    pub fn remove(&mut self, u: usize, v: usize) {
+        let key = (u.min(v), u.max(v));
+        let begin = self.open.get_mut(&key).and_then(Vec::pop).unwrap();
+        self.spans.push((key.0, key.1, begin, self.events.len()));
+        self.events.push(Event::Remove);
+    }
+
+    pub fn query(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Query(u, v));
+    }
+
+    // Answers of the queries, in order
+    pub fn solve(&self) -> Vec<bool> {
+        let t = self.events.len();
+        if t == 0 {
+            return vec![];
+        }
+        let mut spans = self.spans.clone();
+        for (&(u, v), begins) in &self.open {
+            spans.extend(begins.iter().map(|&begin| (u, v, begin, t)));
+        }
+        let mut edges = vec![vec![]; 4 * t];
+        for (u, v, begin, end) in spans {
+            insert(&mut edges, 1, 0, t, (begin, end), (u, v));
+        }
+        let mut forest = RollbackSetForest::new(self.n);
+        let mut answers = vec![];
+        self.dfs(&edges, &mut forest, 1, 0, t, &mut answers);
+        answers
+    }
+
+    fn dfs(
+        &self,
+        edges: &[Vec<(usize, usize)>],
+        forest: &mut RollbackSetForest,
+        i: usize,
+        s: usize,
+        e: usize,
+        answers: &mut Vec<bool>,
+    ) {
+        let snapshot = forest.snapshot();
+        for &(u, v) in &edges[i] {
+            forest.join(u, v);
+        }
+        if e - s == 1 {
+            if let Event::Query(u, v) = self.events[s] {
+                answers.push(forest.same(u, v));
+            }
+        } else {
+            let m = (s + e) / 2;
+            self.dfs(edges, forest, i << 1, s, m, answers);
+            self.dfs(edges, forest, i << 1 | 1, m, e, answers);
+        }
+        forest.rollback(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rollback() {
+        let mut forest = RollbackSetForest::new(5);
+        forest.join(0, 1);
+        let snapshot = forest.snapshot();
+        forest.join(2, 3);
+        forest.join(1, 3);
+        forest.join(0, 2);
+        assert_eq!(4, forest.size(2));
+        assert_eq!(2, forest.component_count());
+        forest.rollback(snapshot);
+        assert_eq!(vec![0, 0, 2, 3, 4], forest.parent);
+        assert_eq!(4, forest.component_count());
+        assert!(forest.same(0, 1));
+        assert!(!forest.same(1, 3));
+    }
+
+    #[test]
+    fn offline_connectivity() {
+        let mut conn = OfflineConnectivity::new(4);
+        conn.add(0, 1);
+        conn.add(1, 2);
+        conn.query(0, 2);
+        conn.remove(1, 0);
+        conn.query(0, 2);
+        conn.add(2, 3);
+        conn.add(0, 3);
+        conn.query(0, 1);
+        conn.add(0, 3);
+        conn.remove(0, 3);
+        conn.query(0, 1);
+        conn.remove(3, 0);
+        conn.query(0, 1);
+        conn.query(3, 1);
+        assert_eq!(vec![true, false, true, true, false, true], conn.solve());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::math::Monoid;
+
 pub trait Operation {
     type Value: Clone;
     type Update;
@@ -7,6 +9,23 @@ pub trait Operation {
     fn apply(v: &mut Self::Value, u: &Self::Update);
 }
 
+// Point update combines the new value into the old one.
+// A type implementing Monoid cannot implement Operation by itself as well.
+impl<M: Monoid> Operation for M {
+    type Value = M::T;
+    type Update = M::T;
+
+    fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+        <M as Monoid>::apply(l, r)
+    }
+    fn i_value() -> Self::Value {
+        M::ident()
+    }
+    fn apply(v: &mut Self::Value, u: &Self::Update) {
+        *v = <M as Monoid>::apply(v, u);
+    }
+}
+
 pub struct SegmentTree<O: Operation> {
     val: Vec<O::Value>,
     n: usize,
@@ -31,23 +50,93 @@ impl<O: Operation> SegmentTree<O> {
         }
     }
 
-    pub fn query(&mut self, mut l: usize, mut r: usize) -> O::Value {
+    pub fn query(&self, mut l: usize, mut r: usize) -> O::Value {
         l += self.n;
         r += self.n;
-        let mut result = O::i_value();
+        let mut left = O::i_value();
+        let mut right = O::i_value();
+        while l < r {
+            if l & 1 == 1 {
+                left = O::combine(&left, &self.val[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = O::combine(&self.val[r], &right);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        O::combine(&left, &right)
+    }
+
+    fn nodes(&self, l: usize, r: usize) -> Vec<usize> {
+        let mut l = l + self.n;
+        let mut r = r + self.n;
+        let mut left = vec![];
+        let mut right = vec![];
         while l < r {
             if l & 1 == 1 {
-                result = O::combine(&result, &self.val[l]);
+                left.push(l);
                 l += 1;
             }
             if r & 1 == 1 {
                 r -= 1;
-                result = O::combine(&self.val[r], &result);
+                right.push(r);
             }
             l >>= 1;
             r >>= 1;
         }
-        result
+        left.extend(right.into_iter().rev());
+        left
+    }
+
+    // Largest r such that pred(query(l, r)) holds.
+    // pred must be monotone and hold for i_value().
+    pub fn max_right(&self, l: usize, mut pred: impl FnMut(&O::Value) -> bool) -> usize {
+        let mut acc = O::i_value();
+        for node in self.nodes(l, self.n) {
+            let next = O::combine(&acc, &self.val[node]);
+            if pred(&next) {
+                acc = next;
+                continue;
+            }
+            let mut i = node;
+            while i < self.n {
+                i <<= 1;
+                let next = O::combine(&acc, &self.val[i]);
+                if pred(&next) {
+                    acc = next;
+                    i += 1;
+                }
+            }
+            return i - self.n;
+        }
+        self.n
+    }
+
+    // Smallest l such that pred(query(l, r)) holds.
+    // pred must be monotone and hold for i_value().
+    pub fn min_left(&self, r: usize, mut pred: impl FnMut(&O::Value) -> bool) -> usize {
+        let mut acc = O::i_value();
+        for node in self.nodes(0, r).into_iter().rev() {
+            let next = O::combine(&self.val[node], &acc);
+            if pred(&next) {
+                acc = next;
+                continue;
+            }
+            let mut i = node;
+            while i < self.n {
+                i = i << 1 | 1;
+                let next = O::combine(&self.val[i], &acc);
+                if pred(&next) {
+                    acc = next;
+                    i -= 1;
+                }
+            }
+            return i + 1 - self.n;
+        }
+        0
     }
 }
 
@@ -72,7 +161,7 @@ mod test {
 
     #[test]
     fn query() {
-        let mut tree = SegmentTree::<SumSet>::new(10, [2, 1, 4, 3, 6, 5, 8, 7, 10, 9]);
+        let tree = SegmentTree::<SumSet>::new(10, [2, 1, 4, 3, 6, 5, 8, 7, 10, 9]);
         assert_eq!(10, tree.query(0, 4));
         assert_eq!(48, tree.query(3, 10));
     }
@@ -86,4 +175,35 @@ mod test {
             tree.val
         );
     }
+
+    #[test]
+    fn max_right() {
+        let data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let tree = SegmentTree::<SumSet>::new(10, data);
+        for l in 0..=10 {
+            for k in 0..60 {
+                let expected = (l..=10)
+                    .take_while(|&r| data[l..r].iter().sum::<i64>() <= k)
+                    .last()
+                    .unwrap();
+                assert_eq!(expected, tree.max_right(l, |&v| v <= k));
+            }
+        }
+    }
+
+    #[test]
+    fn min_left() {
+        let data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let tree = SegmentTree::<SumSet>::new(10, data);
+        for r in 0..=10 {
+            for k in 0..60 {
+                let expected = (0..=r)
+                    .rev()
+                    .take_while(|&l| data[l..r].iter().sum::<i64>() <= k)
+                    .last()
+                    .unwrap();
+                assert_eq!(expected, tree.min_left(r, |&v| v <= k));
+            }
+        }
+    }
 }
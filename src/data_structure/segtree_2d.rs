@@ -0,0 +1,272 @@
+use crate::data_structure::segtree::Operation;
+
+// O::combine must be commutative.
+pub struct SegmentTree2D<O: Operation> {
+    val: Vec<O::Value>,
+    h: usize,
+    w: usize,
+}
+
+impl<O: Operation> SegmentTree2D<O> {
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            val: vec![O::i_value(); 4 * h * w],
+            h,
+            w,
+        }
+    }
+
+    fn at(&self, i: usize, j: usize) -> usize {
+        i * 2 * self.w + j
+    }
+
+    pub fn modify(&mut self, x: usize, y: usize, amount: &O::Update) {
+        let mut i = x + self.h;
+        let leaf = self.at(i, y + self.w);
+        O::apply(&mut self.val[leaf], amount);
+        let mut j = y + self.w;
+        while j > 1 {
+            j >>= 1;
+            let k = self.at(i, j);
+            self.val[k] = O::combine(
+                &self.val[self.at(i, j << 1)],
+                &self.val[self.at(i, j << 1 | 1)],
+            );
+        }
+        while i > 1 {
+            i >>= 1;
+            let mut j = y + self.w;
+            while j >= 1 {
+                let k = self.at(i, j);
+                self.val[k] = O::combine(
+                    &self.val[self.at(i << 1, j)],
+                    &self.val[self.at(i << 1 | 1, j)],
+                );
+                j >>= 1;
+            }
+        }
+    }
+
+    fn query_row(&self, i: usize, mut l: usize, mut r: usize) -> O::Value {
+        l += self.w;
+        r += self.w;
+        let mut result = O::i_value();
+        while l < r {
+            if l & 1 == 1 {
+                result = O::combine(&result, &self.val[self.at(i, l)]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                result = O::combine(&self.val[self.at(i, r)], &result);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        result
+    }
+
+    // [x1, x2) * [y1, y2)
+    pub fn query(&self, mut x1: usize, mut x2: usize, y1: usize, y2: usize) -> O::Value {
+        x1 += self.h;
+        x2 += self.h;
+        let mut result = O::i_value();
+        while x1 < x2 {
+            if x1 & 1 == 1 {
+                result = O::combine(&result, &self.query_row(x1, y1, y2));
+                x1 += 1;
+            }
+            if x2 & 1 == 1 {
+                x2 -= 1;
+                result = O::combine(&self.query_row(x2, y1, y2), &result);
+            }
+            x1 >>= 1;
+            x2 >>= 1;
+        }
+        result
+    }
+}
+
+// 2D segment tree over points given in advance, using O(n log n) memory.
+// O::combine must be commutative.
+pub struct OfflineSegmentTree2D<O: Operation> {
+    xs: Vec<i64>,
+    ys: Vec<Vec<i64>>,
+    val: Vec<Vec<O::Value>>,
+}
+
+impl<O: Operation> OfflineSegmentTree2D<O> {
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let mut xs: Vec<i64> = points.iter().map(|&(x, _)| x).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        let n = xs.len();
+        let mut ys = vec![vec![]; 2 * n];
+        for &(x, y) in points {
+            ys[n + xs.binary_search(&x).unwrap()].push(y);
+        }
+        for i in (1..2 * n).rev() {
+            if i < n {
+                let mut merged = ys[i << 1].clone();
+                merged.extend_from_slice(&ys[i << 1 | 1]);
+                ys[i] = merged;
+            }
+            ys[i].sort_unstable();
+            ys[i].dedup();
+        }
+        let val = ys.iter().map(|y| vec![O::i_value(); 2 * y.len()]).collect();
+        Self { xs, ys, val }
+    }
+
+    fn get(&self, i: usize, y: i64) -> O::Value {
+        match self.ys[i].binary_search(&y) {
+            Ok(j) => self.val[i][self.ys[i].len() + j].clone(),
+            Err(_) => O::i_value(),
+        }
+    }
+
+    // (x, y) must be one of the points given in new().
+    pub fn modify(&mut self, x: i64, y: i64, amount: &O::Update) {
+        let mut i = self.xs.len() + self.xs.binary_search(&x).unwrap();
+        let mut first = true;
+        while i >= 1 {
+            let len = self.ys[i].len();
+            let mut j = len + self.ys[i].binary_search(&y).unwrap();
+            if first {
+                O::apply(&mut self.val[i][j], amount);
+                first = false;
+            } else {
+                self.val[i][j] = O::combine(&self.get(i << 1, y), &self.get(i << 1 | 1, y));
+            }
+            let val = &mut self.val[i];
+            while j > 1 {
+                j >>= 1;
+                val[j] = O::combine(&val[j << 1], &val[j << 1 | 1]);
+            }
+            i >>= 1;
+        }
+    }
+
+    fn query_node(&self, i: usize, y1: i64, y2: i64) -> O::Value {
+        let ys = &self.ys[i];
+        let val = &self.val[i];
+        let mut l = ys.len() + ys.partition_point(|&y| y < y1);
+        let mut r = ys.len() + ys.partition_point(|&y| y < y2);
+        let mut result = O::i_value();
+        while l < r {
+            if l & 1 == 1 {
+                result = O::combine(&result, &val[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                result = O::combine(&val[r], &result);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        result
+    }
+
+    // [x1, x2) * [y1, y2)
+    pub fn query(&self, x1: i64, x2: i64, y1: i64, y2: i64) -> O::Value {
+        let n = self.xs.len();
+        let mut l = n + self.xs.partition_point(|&x| x < x1);
+        let mut r = n + self.xs.partition_point(|&x| x < x2);
+        let mut result = O::i_value();
+        while l < r {
+            if l & 1 == 1 {
+                result = O::combine(&result, &self.query_node(l, y1, y2));
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                result = O::combine(&self.query_node(r, y1, y2), &result);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct MaxSet;
+    impl Operation for MaxSet {
+        type Value = i64;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            *l.max(r)
+        }
+        fn i_value() -> Self::Value {
+            i64::MIN
+        }
+        fn apply(v: &mut Self::Value, u: &Self::Update) {
+            *v = *u;
+        }
+    }
+
+    const POINTS: [(i64, i64, i64); 7] = [
+        (0, 0, 3),
+        (2, 1, 7),
+        (1, 4, 5),
+        (4, 2, 1),
+        (2, 3, 9),
+        (4, 4, 2),
+        (2, 1, 4),
+    ];
+
+    fn brute(points: &[(i64, i64, i64)], x1: i64, x2: i64, y1: i64, y2: i64) -> i64 {
+        let mut latest = std::collections::HashMap::new();
+        for &(x, y, v) in points {
+            latest.insert((x, y), v);
+        }
+        latest
+            .into_iter()
+            .filter(|&((x, y), _)| x1 <= x && x < x2 && y1 <= y && y < y2)
+            .map(|(_, v)| v)
+            .max()
+            .unwrap_or(i64::MIN)
+    }
+
+    #[test]
+    fn query() {
+        let mut tree = SegmentTree2D::<MaxSet>::new(5, 5);
+        for &(x, y, v) in &POINTS {
+            tree.modify(x as usize, y as usize, &v);
+        }
+        for x1 in 0..=5 {
+            for x2 in x1..=5 {
+                for y1 in 0..=5 {
+                    for y2 in y1..=5 {
+                        assert_eq!(
+                            brute(&POINTS, x1, x2, y1, y2),
+                            tree.query(x1 as usize, x2 as usize, y1 as usize, y2 as usize)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn offline_query() {
+        let points: Vec<(i64, i64)> = POINTS.iter().map(|&(x, y, _)| (x, y)).collect();
+        let mut tree = OfflineSegmentTree2D::<MaxSet>::new(&points);
+        for &(x, y, v) in &POINTS {
+            tree.modify(x, y, &v);
+        }
+        for x1 in -1..=5 {
+            for x2 in x1..=6 {
+                for y1 in -1..=5 {
+                    for y2 in y1..=6 {
+                        assert_eq!(brute(&POINTS, x1, x2, y1, y2), tree.query(x1, x2, y1, y2));
+                    }
+                }
+            }
+        }
+    }
+}
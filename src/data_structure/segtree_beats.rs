@@ -0,0 +1,398 @@
+pub trait Operation {
+    type Value: Clone;
+    type Update: Clone;
+
+    fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value;
+    fn i_value() -> Self::Value;
+    fn compose(a: &mut Self::Update, b: &Self::Update);
+    fn is_i(u: &Self::Update) -> bool;
+    fn i_update() -> Self::Update;
+    // Returns false if u cannot be applied to v as a whole (the break condition),
+    // in which case v is recomputed from its children. Must succeed if len is 1.
+    fn apply(v: &mut Self::Value, len: usize, u: &Self::Update) -> bool;
+}
+
+pub struct SegmentTreeBeats<O: Operation> {
+    val: Vec<O::Value>,
+    lazy: Vec<O::Update>,
+    n: usize,
+}
+
+impl<O: Operation> SegmentTreeBeats<O> {
+    pub fn new(n: usize, data: impl IntoIterator<Item = O::Value>) -> Self {
+        let size = n.next_power_of_two();
+        let mut tree = Self {
+            val: vec![O::i_value(); size << 1],
+            lazy: vec![O::i_update(); size << 1],
+            n,
+        };
+        let data: Vec<O::Value> = data.into_iter().take(n).collect();
+        if n > 0 {
+            tree.build(1, 0, n, &data);
+        }
+        tree
+    }
+
+    fn build(&mut self, i: usize, s: usize, e: usize, data: &[O::Value]) {
+        if e - s == 1 {
+            if let Some(v) = data.get(s) {
+                self.val[i] = v.clone();
+            }
+            return;
+        }
+        let m = (s + e) / 2;
+        self.build(i << 1, s, m, data);
+        self.build(i << 1 | 1, m, e, data);
+        self.val[i] = O::combine(&self.val[i << 1], &self.val[i << 1 | 1]);
+    }
+
+    fn push(&mut self, i: usize, s: usize, e: usize) {
+        if !O::is_i(&self.lazy[i]) {
+            let amount = std::mem::replace(&mut self.lazy[i], O::i_update());
+            let m = (s + e) / 2;
+            self.apply(i << 1, s, m, &amount);
+            self.apply(i << 1 | 1, m, e, &amount);
+        }
+    }
+
+    fn apply(&mut self, i: usize, s: usize, e: usize, amount: &O::Update) {
+        if O::apply(&mut self.val[i], e - s, amount) {
+            if e - s > 1 {
+                O::compose(&mut self.lazy[i], amount);
+            }
+        } else {
+            assert!(e - s > 1, "update failed to apply to a single element");
+            self.push(i, s, e);
+            let m = (s + e) / 2;
+            self.apply(i << 1, s, m, amount);
+            self.apply(i << 1 | 1, m, e, amount);
+            self.val[i] = O::combine(&self.val[i << 1], &self.val[i << 1 | 1]);
+        }
+    }
+
+    pub fn modify(&mut self, l: usize, r: usize, amount: &O::Update) {
+        if l < r {
+            self.modify_at(1, 0, self.n, l, r, amount);
+        }
+    }
+
+    fn modify_at(&mut self, i: usize, s: usize, e: usize, l: usize, r: usize, amount: &O::Update) {
+        if r <= s || e <= l {
+            return;
+        }
+        if l <= s && e <= r {
+            self.apply(i, s, e, amount);
+            return;
+        }
+        self.push(i, s, e);
+        let m = (s + e) / 2;
+        self.modify_at(i << 1, s, m, l, r, amount);
+        self.modify_at(i << 1 | 1, m, e, l, r, amount);
+        self.val[i] = O::combine(&self.val[i << 1], &self.val[i << 1 | 1]);
+    }
+
+    // Pending updates are carried down and applied to the visited nodes on the fly.
+    pub fn query(&self, l: usize, r: usize) -> O::Value {
+        if l < r {
+            self.query_at(1, 0, self.n, l, r, &O::i_update())
+        } else {
+            O::i_value()
+        }
+    }
+
+    // pending: updates not pushed down from the ancestors of i yet
+    fn query_at(
+        &self,
+        i: usize,
+        s: usize,
+        e: usize,
+        l: usize,
+        r: usize,
+        pending: &O::Update,
+    ) -> O::Value {
+        if r <= s || e <= l {
+            return O::i_value();
+        }
+        if l <= s && e <= r {
+            let mut v = self.val[i].clone();
+            if O::is_i(pending) || O::apply(&mut v, e - s, pending) {
+                return v;
+            }
+        }
+        let mut amount = self.lazy[i].clone();
+        O::compose(&mut amount, pending);
+        let m = (s + e) / 2;
+        O::combine(
+            &self.query_at(i << 1, s, m, l, r, &amount),
+            &self.query_at(i << 1 | 1, m, e, l, r, &amount),
+        )
+    }
+}
+
+// Aggregate of a range, tracking the two smallest and largest distinct values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinMaxSum {
+    pub min: i64,
+    pub max: i64,
+    pub sum: i64,
+    min2: i64,
+    min_count: usize,
+    max2: i64,
+    max_count: usize,
+}
+
+impl MinMaxSum {
+    pub fn new(v: i64) -> Self {
+        Self {
+            min: v,
+            max: v,
+            sum: v,
+            min2: i64::MAX,
+            min_count: 1,
+            max2: i64::MIN,
+            max_count: 1,
+        }
+    }
+}
+
+// x -> min(max(x + add, lo), hi), where lo <= hi
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Clamp {
+    add: i64,
+    lo: i64,
+    hi: i64,
+}
+
+impl Clamp {
+    pub fn add(add: i64) -> Self {
+        Self {
+            add,
+            lo: i64::MIN,
+            hi: i64::MAX,
+        }
+    }
+
+    pub fn chmin(hi: i64) -> Self {
+        Self {
+            add: 0,
+            lo: i64::MIN,
+            hi,
+        }
+    }
+
+    pub fn chmax(lo: i64) -> Self {
+        Self {
+            add: 0,
+            lo,
+            hi: i64::MAX,
+        }
+    }
+}
+
+// Range chmin, chmax, add and range min, max, sum
+pub struct ChminChmaxAddSum;
+
+impl Operation for ChminChmaxAddSum {
+    type Value = MinMaxSum;
+    type Update = Clamp;
+
+    fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+        let (min, min_count, min2) = match l.min.cmp(&r.min) {
+            std::cmp::Ordering::Less => (l.min, l.min_count, l.min2.min(r.min)),
+            std::cmp::Ordering::Greater => (r.min, r.min_count, r.min2.min(l.min)),
+            std::cmp::Ordering::Equal => (l.min, l.min_count + r.min_count, l.min2.min(r.min2)),
+        };
+        let (max, max_count, max2) = match l.max.cmp(&r.max) {
+            std::cmp::Ordering::Greater => (l.max, l.max_count, l.max2.max(r.max)),
+            std::cmp::Ordering::Less => (r.max, r.max_count, r.max2.max(l.max)),
+            std::cmp::Ordering::Equal => (l.max, l.max_count + r.max_count, l.max2.max(r.max2)),
+        };
+        MinMaxSum {
+            min,
+            max,
+            sum: l.sum + r.sum,
+            min2,
+            min_count,
+            max2,
+            max_count,
+        }
+    }
+
+    fn i_value() -> Self::Value {
+        MinMaxSum {
+            min: i64::MAX,
+            max: i64::MIN,
+            sum: 0,
+            min2: i64::MAX,
+            min_count: 0,
+            max2: i64::MIN,
+            max_count: 0,
+        }
+    }
+
+    fn compose(a: &mut Self::Update, b: &Self::Update) {
+        a.add += b.add;
+        a.lo = a.lo.saturating_add(b.add).clamp(b.lo, b.hi);
+        a.hi = a.hi.saturating_add(b.add).clamp(b.lo, b.hi);
+    }
+
+    fn is_i(u: &Self::Update) -> bool {
+        *u == Self::i_update()
+    }
+
+    fn i_update() -> Self::Update {
+        Clamp::add(0)
+    }
+
+    fn apply(v: &mut Self::Value, len: usize, u: &Self::Update) -> bool {
+        if v.min_count == 0 {
+            return true;
+        }
+        if u.add != 0 {
+            v.min += u.add;
+            v.max += u.add;
+            v.sum += u.add * len as i64;
+            if v.min2 != i64::MAX {
+                v.min2 += u.add;
+            }
+            if v.max2 != i64::MIN {
+                v.max2 += u.add;
+            }
+        }
+        // min2 and max2 are only meaningful if there are two distinct values.
+        if u.lo > v.min {
+            if v.min != v.max && u.lo >= v.min2 {
+                return false;
+            }
+            // Wrapping, as only the resulting sum has to fit in i64
+            let delta = u.lo.wrapping_sub(v.min).wrapping_mul(v.min_count as i64);
+            v.sum = v.sum.wrapping_add(delta);
+            if v.max == v.min {
+                v.max = u.lo;
+            } else if v.max2 == v.min {
+                v.max2 = u.lo;
+            }
+            v.min = u.lo;
+        }
+        if u.hi < v.max {
+            if v.min != v.max && u.hi <= v.max2 {
+                return false;
+            }
+            let delta = u.hi.wrapping_sub(v.max).wrapping_mul(v.max_count as i64);
+            v.sum = v.sum.wrapping_add(delta);
+            if v.min == v.max {
+                v.min = u.hi;
+            } else if v.min2 == v.max {
+                v.min2 = u.hi;
+            }
+            v.max = u.hi;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn query() {
+        let data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        let mut tree =
+            SegmentTreeBeats::<ChminChmaxAddSum>::new(10, data.iter().map(|&v| MinMaxSum::new(v)));
+        tree.modify(0, 10, &Clamp::chmin(6));
+        assert_eq!(45, tree.query(0, 10).sum);
+        tree.modify(2, 8, &Clamp::chmax(5));
+        assert_eq!(
+            vec![2, 1, 5, 5, 6, 5, 6, 6, 6, 6],
+            (0..10)
+                .map(|i| tree.query(i, i + 1).sum)
+                .collect::<Vec<_>>()
+        );
+        tree.modify(1, 4, &Clamp::add(-3));
+        assert_eq!(-2, tree.query(1, 2).min);
+        assert_eq!(6, tree.query(0, 10).max);
+        assert_eq!(39, tree.query(0, 10).sum);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = 37;
+        let mut data: Vec<i64> = (0..n).map(|_| rng.gen_range(-50..50)).collect();
+        let mut tree =
+            SegmentTreeBeats::<ChminChmaxAddSum>::new(n, data.iter().map(|&v| MinMaxSum::new(v)));
+        for _ in 0..2000 {
+            let l = rng.gen_range(0..n);
+            let r = rng.gen_range(l + 1..=n);
+            let x = rng.gen_range(-50..50);
+            match rng.gen_range(0..4) {
+                0 => {
+                    tree.modify(l, r, &Clamp::chmin(x));
+                    data[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+                }
+                1 => {
+                    tree.modify(l, r, &Clamp::chmax(x));
+                    data[l..r].iter_mut().for_each(|v| *v = (*v).max(x));
+                }
+                2 => {
+                    tree.modify(l, r, &Clamp::add(x));
+                    data[l..r].iter_mut().for_each(|v| *v += x);
+                }
+                _ => {
+                    let result = tree.query(l, r);
+                    assert_eq!(data[l..r].iter().sum::<i64>(), result.sum);
+                    assert_eq!(*data[l..r].iter().min().unwrap(), result.min);
+                    assert_eq!(*data[l..r].iter().max().unwrap(), result.max);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn query_shared() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 23;
+        let mut data: Vec<i64> = (0..n).map(|_| rng.gen_range(-50..50)).collect();
+        let mut tree =
+            SegmentTreeBeats::<ChminChmaxAddSum>::new(n, data.iter().map(|&v| MinMaxSum::new(v)));
+        for (l, r, x) in [(0, 23, 20), (3, 15, -30), (5, 9, 7), (10, 23, 0)] {
+            tree.modify(l, r, &Clamp::chmin(x));
+            data[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+            tree.modify(r / 2, r, &Clamp::add(x));
+            data[r / 2..r].iter_mut().for_each(|v| *v += x);
+        }
+        let (tree, data) = (&tree, &data);
+        std::thread::scope(|s| {
+            for l in 0..n {
+                s.spawn(move || {
+                    for r in l + 1..=n {
+                        let result = tree.query(l, r);
+                        assert_eq!(data[l..r].iter().sum::<i64>(), result.sum);
+                        assert_eq!(*data[l..r].iter().min().unwrap(), result.min);
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn extreme_clamp() {
+        let data = [-5, 7, -3];
+        let mut tree =
+            SegmentTreeBeats::<ChminChmaxAddSum>::new(3, data.iter().map(|&v| MinMaxSum::new(v)));
+        tree.modify(1, 2, &Clamp::chmax(i64::MAX));
+        assert_eq!(i64::MAX, tree.query(1, 2).max);
+        tree.modify(0, 1, &Clamp::chmin(i64::MIN));
+        assert_eq!(i64::MIN, tree.query(0, 1).min);
+        let all = tree.query(0, 3);
+        assert_eq!((i64::MIN, i64::MAX, -4), (all.min, all.max, all.sum));
+
+        let mut tree = SegmentTreeBeats::<ChminChmaxAddSum>::new(1, [MinMaxSum::new(0)]);
+        tree.modify(0, 1, &Clamp::chmax(i64::MAX));
+        assert_eq!(i64::MAX, tree.query(0, 1).sum);
+        tree.modify(0, 1, &Clamp::chmin(i64::MIN));
+        assert_eq!(i64::MIN, tree.query(0, 1).sum);
+    }
+}
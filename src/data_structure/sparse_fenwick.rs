@@ -0,0 +1,70 @@
+use crate::data_structure::fenwick::Operation;
+use std::collections::HashMap;
+
+// Fenwick tree over [1, n] storing only the nodes that were modified.
+pub struct SparseFenwickTree<O: Operation> {
+    val: HashMap<u64, O::Value>,
+    n: u64,
+}
+
+impl<O: Operation> SparseFenwickTree<O> {
+    pub fn new(n: u64) -> Self {
+        Self {
+            val: HashMap::new(),
+            n,
+        }
+    }
+
+    // i: 1-based index
+    pub fn modify(&mut self, mut i: u64, amount: &O::Update) {
+        while i != 0 && i <= self.n {
+            O::apply(self.val.entry(i).or_insert_with(O::i_value), amount);
+            i = (i | (i - 1)).wrapping_add(1);
+        }
+    }
+
+    pub fn query(&self, mut count: u64) -> O::Value {
+        let mut result = O::i_value();
+        while count > 0 {
+            if let Some(v) = self.val.get(&count) {
+                result = O::combine(v, &result);
+            }
+            count &= count - 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct SumAdd;
+    impl Operation for SumAdd {
+        type Value = i64;
+        type Update = i64;
+        fn combine(l: &Self::Value, r: &Self::Value) -> Self::Value {
+            l + r
+        }
+        fn i_value() -> Self::Value {
+            0
+        }
+        fn apply(v: &mut Self::Value, u: &Self::Update) {
+            *v += *u;
+        }
+    }
+
+    #[test]
+    fn query() {
+        let mut tree = SparseFenwickTree::<SumAdd>::new(u64::MAX);
+        tree.modify(1, &3);
+        tree.modify(1_000_000_000_000, &5);
+        tree.modify(u64::MAX, &7);
+        tree.modify(1 << 63, &-2);
+        assert_eq!(0, tree.query(0));
+        assert_eq!(3, tree.query(999_999_999_999));
+        assert_eq!(8, tree.query(1_000_000_000_000));
+        assert_eq!(6, tree.query(1 << 63));
+        assert_eq!(13, tree.query(u64::MAX));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::math::Group;
+
+// Disjoint set forest where each item has a potential over G relative to the
+// root of its set, i.e. pot(i) = pot(parent) * weight[i].
+pub struct WeightedSetForest<G: Group> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    weight: Vec<G::T>,
+}
+
+impl<G: Group> WeightedSetForest<G> {
+    pub fn new(s: usize) -> Self {
+        Self {
+            parent: (0..s).collect(),
+            size: vec![1; s],
+            weight: vec![G::ident(); s],
+        }
+    }
+
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut path = vec![];
+        let mut root = i;
+        while root != self.parent[root] {
+            path.push(root);
+            root = self.parent[root];
+        }
+        // Full path compression, accumulating potentials from the top
+        let mut acc = G::ident();
+        for &node in path.iter().rev() {
+            acc = G::apply(&acc, &self.weight[node]);
+            self.weight[node] = acc.clone();
+            self.parent[node] = root;
+        }
+        root
+    }
+
+    // Potential of i relative to the root of its set
+    pub fn potential(&mut self, i: usize) -> G::T {
+        self.find(i);
+        self.weight[i].clone()
+    }
+
+    // Records pot(u)^-1 * pot(v) = w.
+    // Returns if two items were in the same set, or the known pot(u)^-1 * pot(v)
+    // if it contradicts w.
+    pub fn join(&mut self, u: usize, v: usize, w: &G::T) -> Result<bool, G::T> {
+        let ru = self.find(u);
+        let rv = self.find(v);
+        let pu = self.weight[u].clone();
+        let pv = self.weight[v].clone();
+        if ru == rv {
+            let known = G::apply(&G::inverse(&pu), &pv);
+            return if &known == w { Ok(true) } else { Err(known) };
+        }
+        // Potential of rv relative to ru
+        let rel = G::apply(&G::apply(&pu, w), &G::inverse(&pv));
+        if self.size[ru] < self.size[rv] {
+            self.parent[ru] = rv;
+            self.size[rv] += self.size[ru];
+            self.weight[ru] = G::inverse(&rel);
+        } else {
+            self.parent[rv] = ru;
+            self.size[ru] += self.size[rv];
+            self.weight[rv] = rel;
+        }
+        Ok(false)
+    }
+
+    // pot(u)^-1 * pot(v) if two items are in the same set
+    pub fn diff(&mut self, u: usize, v: usize) -> Option<G::T> {
+        if self.find(u) == self.find(v) {
+            Some(G::apply(&G::inverse(&self.weight[u]), &self.weight[v]))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::Monoid;
+
+    struct Sum;
+    impl Monoid for Sum {
+        type T = i64;
+        fn apply(l: &i64, r: &i64) -> i64 {
+            l + r
+        }
+        fn ident() -> i64 {
+            0
+        }
+    }
+    impl Group for Sum {
+        fn inverse(a: &i64) -> i64 {
+            -a
+        }
+    }
+
+    struct Parity;
+    impl Monoid for Parity {
+        type T = bool;
+        fn apply(l: &bool, r: &bool) -> bool {
+            l ^ r
+        }
+        fn ident() -> bool {
+            false
+        }
+    }
+    impl Group for Parity {
+        fn inverse(a: &bool) -> bool {
+            *a
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let mut forest = WeightedSetForest::<Sum>::new(6);
+        assert_eq!(Ok(false), forest.join(0, 1, &3));
+        assert_eq!(Ok(false), forest.join(2, 3, &-2));
+        assert_eq!(Ok(false), forest.join(1, 3, &10));
+        assert_eq!(Ok(false), forest.join(5, 4, &1));
+        assert_eq!(Some(3), forest.diff(0, 1));
+        assert_eq!(Some(15), forest.diff(0, 2));
+        assert_eq!(Some(-13), forest.diff(3, 0));
+        assert_eq!(None, forest.diff(0, 4));
+        assert_eq!(Ok(true), forest.join(2, 0, &-15));
+        assert_eq!(Err(-15), forest.join(2, 0, &-14));
+        assert_eq!(Ok(false), forest.join(4, 3, &0));
+        assert_eq!(Some(-9), forest.diff(5, 1));
+    }
+
+    #[test]
+    fn bipartite() {
+        // Odd cycle 0-1-2 and even cycle 3-4-5-6
+        let mut forest = WeightedSetForest::<Parity>::new(7);
+        assert!(forest.join(0, 1, &true).is_ok());
+        assert!(forest.join(1, 2, &true).is_ok());
+        assert!(forest.join(2, 0, &true).is_err());
+        assert!(forest.join(3, 4, &true).is_ok());
+        assert!(forest.join(4, 5, &true).is_ok());
+        assert!(forest.join(5, 6, &true).is_ok());
+        assert!(forest.join(6, 3, &true).is_ok());
+        assert_eq!(Some(false), forest.diff(3, 5));
+    }
+}
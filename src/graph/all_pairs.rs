@@ -0,0 +1,250 @@
+use crate::data_structure::Graph;
+use crate::graph::{bellman_ford, dijkstra, Weight, WeightedEdge};
+
+pub struct DistanceMatrix<W> {
+    n: usize,
+    dist: Vec<Option<W>>,
+    // Node after u on the shortest path from u to v
+    next: Vec<Option<usize>>,
+}
+
+impl<W: Weight> DistanceMatrix<W> {
+    fn new(n: usize) -> Self {
+        let mut dist = vec![None; n * n];
+        for i in 0..n {
+            dist[i * n + i] = Some(W::ZERO);
+        }
+        Self {
+            n,
+            dist,
+            next: vec![None; n * n],
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.n
+    }
+
+    // None if unreachable
+    pub fn dist(&self, u: usize, v: usize) -> Option<W> {
+        self.dist[u * self.n + v]
+    }
+
+    // Nodes from u to v, both inclusive
+    pub fn path(&self, u: usize, v: usize) -> Option<Path<'_, W>> {
+        self.dist(u, v)?;
+        Some(Path {
+            matrix: self,
+            now: Some(u),
+            to: v,
+        })
+    }
+}
+
+pub struct Path<'a, W> {
+    matrix: &'a DistanceMatrix<W>,
+    now: Option<usize>,
+    to: usize,
+}
+
+impl<W> Iterator for Path<'_, W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let now = self.now?;
+        self.now = if now == self.to {
+            None
+        } else {
+            self.matrix.next[now * self.matrix.n + self.to]
+        };
+        Some(now)
+    }
+}
+
+// O(V^3), None if there is a negative cycle.
+pub fn floyd_warshall<T: WeightedEdge>(graph: &Graph<T>) -> Option<DistanceMatrix<T::Weight>> {
+    let n = graph.node_count();
+    let mut matrix = DistanceMatrix::new(n);
+    for u in 0..n {
+        for (v, edge) in graph.neighbors(u) {
+            let w = edge.weight();
+            if matrix.dist[u * n + v].is_none_or(|old| w < old) {
+                matrix.dist[u * n + v] = Some(w);
+                matrix.next[u * n + v] = Some(v);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let Some(ik) = matrix.dist[i * n + k] else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(kj) = matrix.dist[k * n + j] else {
+                    continue;
+                };
+                let nd = ik + kj;
+                if matrix.dist[i * n + j].is_none_or(|old| nd < old) {
+                    matrix.dist[i * n + j] = Some(nd);
+                    matrix.next[i * n + j] = matrix.next[i * n + k];
+                }
+            }
+        }
+        // Bail out early before negative cycles blow the distances up.
+        if (0..n).any(|i| matrix.dist[i * n + i] < Some(T::Weight::ZERO)) {
+            return None;
+        }
+    }
+    Some(matrix)
+}
+
+// O(VE log V) by reweighting edges to be non-negative with Bellman-Ford
+// potentials, then running Dijkstra from every node.
+// None if there is a negative cycle.
+pub fn johnson<T: WeightedEdge>(graph: &Graph<T>) -> Option<DistanceMatrix<T::Weight>> {
+    let n = graph.node_count();
+    // Virtual node n connected to every node by 0
+    let mut virtual_graph = Graph::new(n + 1, graph.edge_count() + n);
+    for u in 0..n {
+        for (v, edge) in graph.neighbors(u) {
+            virtual_graph.connect(u, v, edge.weight());
+        }
+        virtual_graph.connect(n, u, T::Weight::ZERO);
+    }
+    let potential = bellman_ford(&virtual_graph, n).ok()?;
+    let h: Vec<_> = (0..n).map(|i| potential.dist(i).unwrap()).collect();
+
+    let mut reweighted = Graph::new(n, graph.edge_count());
+    for u in 0..n {
+        for (v, edge) in graph.neighbors(u) {
+            reweighted.connect(u, v, edge.weight() + h[u] - h[v]);
+        }
+    }
+    let mut matrix = DistanceMatrix::new(n);
+    for s in 0..n {
+        let paths = dijkstra(&reweighted, s);
+        let row = s * n;
+        for v in 0..n {
+            matrix.dist[row + v] = paths.dist(v).map(|d| d + h[v] - h[s]);
+        }
+        // The first hop of v is that of its parent, unless the parent is s.
+        for v in 0..n {
+            let mut stack = vec![];
+            let mut now = v;
+            while let Some(prev) = paths.prev(now) {
+                if prev == s {
+                    matrix.next[row + now] = Some(now);
+                    break;
+                }
+                if matrix.next[row + now].is_some() {
+                    break;
+                }
+                stack.push(now);
+                now = prev;
+            }
+            let first = matrix.next[row + now];
+            for node in stack {
+                matrix.next[row + node] = first;
+            }
+        }
+    }
+    Some(matrix)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_graph() -> Graph<i64> {
+        let mut graph = Graph::new(5, 9);
+        graph.connect(0, 1, 3);
+        graph.connect(0, 2, 8);
+        graph.connect(0, 4, -4);
+        graph.connect(1, 3, 1);
+        graph.connect(1, 4, 7);
+        graph.connect(2, 1, 4);
+        graph.connect(3, 0, 2);
+        graph.connect(3, 2, -5);
+        graph.connect(4, 3, 6);
+        graph
+    }
+
+    const EXPECTED: [[i64; 5]; 5] = [
+        [0, 1, -3, 2, -4],
+        [3, 0, -4, 1, -1],
+        [7, 4, 0, 5, 3],
+        [2, -1, -5, 0, -2],
+        [8, 5, 1, 6, 0],
+    ];
+
+    fn check(matrix: &DistanceMatrix<i64>, graph: &Graph<i64>) {
+        for (u, row) in EXPECTED.iter().enumerate() {
+            for (v, &expected) in row.iter().enumerate() {
+                assert_eq!(Some(expected), matrix.dist(u, v));
+                let path: Vec<_> = matrix.path(u, v).unwrap().collect();
+                assert_eq!((u, v), (path[0], *path.last().unwrap()));
+                let len: i64 = path
+                    .windows(2)
+                    .map(|e| {
+                        graph
+                            .neighbors(e[0])
+                            .filter(|&(next, _)| next == e[1])
+                            .map(|(_, &w)| w)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert_eq!(expected, len);
+            }
+        }
+    }
+
+    #[test]
+    fn floyd_warshall() {
+        let graph = test_graph();
+        let matrix = super::floyd_warshall(&graph).unwrap();
+        check(&matrix, &graph);
+        assert_eq!(
+            vec![4, 3, 2, 1],
+            matrix.path(4, 1).unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![2], matrix.path(2, 2).unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn johnson() {
+        let graph = test_graph();
+        let matrix = super::johnson(&graph).unwrap();
+        check(&matrix, &graph);
+        assert_eq!(
+            vec![4, 3, 2, 1],
+            matrix.path(4, 1).unwrap().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut graph = Graph::new(3, 2);
+        graph.connect(0, 1, 2u32);
+        graph.connect(1, 0, 3);
+        for matrix in [
+            super::floyd_warshall(&graph).unwrap(),
+            super::johnson(&graph).unwrap(),
+        ] {
+            assert_eq!(Some(0), matrix.dist(0, 0));
+            assert_eq!(Some(3), matrix.dist(1, 0));
+            assert_eq!(None, matrix.dist(0, 2));
+            assert!(matrix.path(2, 1).is_none());
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = Graph::new(3, 3);
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, -3);
+        graph.connect(2, 0, 1);
+        assert!(super::floyd_warshall(&graph).is_none());
+        assert!(super::johnson(&graph).is_none());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::data_structure::Graph;
+use crate::graph::{ShortestPaths, Weight, WeightedEdge};
+
+// Relaxes every edge until nothing changes, stopping early.
+// Returns a node on or reachable from a negative cycle, if any.
+fn relax<T: WeightedEdge>(
+    graph: &Graph<T>,
+    dist: &mut [Option<T::Weight>],
+    prev: &mut [Option<usize>],
+) -> Option<usize> {
+    let n = graph.node_count();
+    let mut updated = None;
+    for _ in 0..n {
+        updated = None;
+        for now in 0..n {
+            let Some(d) = dist[now] else {
+                continue;
+            };
+            for (next, edge) in graph.neighbors(now) {
+                let nd = d + edge.weight();
+                if dist[next].is_none_or(|old| nd < old) {
+                    dist[next] = Some(nd);
+                    prev[next] = Some(now);
+                    updated = Some(next);
+                }
+            }
+        }
+        updated?;
+    }
+    // Still relaxing after n rounds
+    updated
+}
+
+fn cycle(prev: &[Option<usize>], mut now: usize) -> Vec<usize> {
+    for _ in 0..prev.len() {
+        now = prev[now].unwrap();
+    }
+    let start = now;
+    let mut cycle = vec![start];
+    now = prev[start].unwrap();
+    while now != start {
+        cycle.push(now);
+        now = prev[now].unwrap();
+    }
+    cycle.reverse();
+    cycle
+}
+
+// Shortest paths allowing negative weights, or a negative cycle reachable
+// from the source, as nodes in the order of its edges.
+pub fn bellman_ford<T: WeightedEdge>(
+    graph: &Graph<T>,
+    source: usize,
+) -> Result<ShortestPaths<T::Weight>, Vec<usize>> {
+    let mut dist = vec![None; graph.node_count()];
+    let mut prev = vec![None; graph.node_count()];
+    dist[source] = Some(T::Weight::ZERO);
+    match relax(graph, &mut dist, &mut prev) {
+        Some(now) => Err(cycle(&prev, now)),
+        None => Ok(ShortestPaths { dist, prev }),
+    }
+}
+
+// Constraints of the form x[v] - x[u] <= w
+pub struct DifferenceConstraints<W: Weight> {
+    graph: Graph<W>,
+}
+
+impl<W: Weight> DifferenceConstraints<W> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            graph: Graph::new(n, 0),
+        }
+    }
+
+    // x[v] - x[u] <= w
+    pub fn add(&mut self, u: usize, v: usize, w: W) {
+        self.graph.connect(u, v, w);
+    }
+
+    // An assignment satisfying every constraint with every x <= 0, if any
+    pub fn solve(&self) -> Option<Vec<W>> {
+        // Every node is a source, as if connected from a virtual node by 0.
+        let mut dist = vec![Some(W::ZERO); self.graph.node_count()];
+        let mut prev = vec![None; self.graph.node_count()];
+        match relax(&self.graph, &mut dist, &mut prev) {
+            Some(_) => None,
+            None => Some(dist.into_iter().map(Option::unwrap).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dist() {
+        let mut graph = Graph::new(5, 6);
+        graph.connect(0, 1, 4);
+        graph.connect(0, 2, 5);
+        graph.connect(1, 2, -3);
+        graph.connect(2, 3, 4);
+        graph.connect(3, 1, 2);
+        graph.connect(4, 0, 1);
+        let paths = bellman_ford(&graph, 0).ok().unwrap();
+        assert_eq!(
+            vec![Some(0), Some(4), Some(1), Some(5), None],
+            (0..5).map(|i| paths.dist(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(vec![0, 1, 2, 3]), paths.path(3));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = Graph::new(6, 7);
+        graph.connect(0, 1, 1);
+        graph.connect(1, 2, 1);
+        graph.connect(2, 3, -2);
+        graph.connect(3, 4, 1);
+        graph.connect(4, 2, -1);
+        graph.connect(4, 5, 1);
+        graph.connect(5, 0, 10);
+        let mut cycle = bellman_ford(&graph, 0).err().unwrap();
+        let start = cycle.iter().position(|&v| v == 2).unwrap();
+        cycle.rotate_left(start);
+        assert_eq!(vec![2, 3, 4], cycle);
+        assert!(bellman_ford(&graph, 5).is_err());
+
+        let mut graph = Graph::new(3, 2);
+        graph.connect(1, 2, -1);
+        graph.connect(2, 1, -1);
+        assert!(bellman_ford(&graph, 0).is_ok());
+    }
+
+    #[test]
+    fn difference_constraints() {
+        let mut constraints = DifferenceConstraints::new(4);
+        constraints.add(0, 1, 3);
+        constraints.add(1, 2, -2);
+        constraints.add(2, 0, 1);
+        constraints.add(3, 2, 0);
+        constraints.add(0, 3, 5);
+        let x = constraints.solve().unwrap();
+        for (u, v, w) in [(0, 1, 3), (1, 2, -2), (2, 0, 1), (3, 2, 0), (0, 3, 5)] {
+            assert!(x[v] - x[u] <= w);
+        }
+        constraints.add(2, 1, 2);
+        assert!(constraints.solve().is_some());
+        constraints.add(1, 0, -4);
+        assert!(constraints.solve().is_none());
+    }
+}
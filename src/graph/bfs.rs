@@ -0,0 +1,114 @@
+use crate::data_structure::Graph;
+use crate::graph::{ShortestPaths, Weight, WeightedEdge};
+use std::collections::VecDeque;
+
+// Distances in number of edges
+pub fn bfs<T>(graph: &Graph<T>, source: usize) -> ShortestPaths<usize> {
+    multi_source_bfs(graph, [source])
+}
+
+// Distances from the nearest source
+pub fn multi_source_bfs<T>(
+    graph: &Graph<T>,
+    sources: impl IntoIterator<Item = usize>,
+) -> ShortestPaths<usize> {
+    let mut dist = vec![None; graph.node_count()];
+    let mut prev = vec![None; graph.node_count()];
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if dist[source].is_none() {
+            dist[source] = Some(0);
+            queue.push_back(source);
+        }
+    }
+    while let Some(now) = queue.pop_front() {
+        let nd = dist[now].unwrap() + 1;
+        for (next, _) in graph.neighbors(now) {
+            if dist[next].is_none() {
+                dist[next] = Some(nd);
+                prev[next] = Some(now);
+                queue.push_back(next);
+            }
+        }
+    }
+    ShortestPaths { dist, prev }
+}
+
+// Weights must be either 0 or 1.
+pub fn zero_one_bfs<T: WeightedEdge>(graph: &Graph<T>, source: usize) -> ShortestPaths<T::Weight> {
+    let mut dist = vec![None; graph.node_count()];
+    let mut prev = vec![None; graph.node_count()];
+    let mut deque = VecDeque::new();
+    dist[source] = Some(T::Weight::ZERO);
+    deque.push_back((T::Weight::ZERO, source));
+    while let Some((d, now)) = deque.pop_front() {
+        if dist[now] != Some(d) {
+            continue;
+        }
+        for (next, edge) in graph.neighbors(now) {
+            let w = edge.weight();
+            let nd = d + w;
+            if dist[next].is_none_or(|old| nd < old) {
+                dist[next] = Some(nd);
+                prev[next] = Some(now);
+                if w == T::Weight::ZERO {
+                    deque.push_front((nd, next));
+                } else {
+                    deque.push_back((nd, next));
+                }
+            }
+        }
+    }
+    ShortestPaths { dist, prev }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::dijkstra;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn bfs() {
+        let mut graph = Graph::new(6, 6);
+        graph.connect(0, 1, ());
+        graph.connect(0, 2, ());
+        graph.connect(1, 3, ());
+        graph.connect(2, 3, ());
+        graph.connect(3, 4, ());
+        graph.connect(5, 0, ());
+        let paths = super::bfs(&graph, 0);
+        assert_eq!(
+            vec![Some(0), Some(1), Some(1), Some(2), Some(3), None],
+            (0..6).map(|i| paths.dist(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(4, paths.path(4).unwrap().len());
+        assert_eq!(None, paths.prev(0));
+
+        let paths = multi_source_bfs(&graph, [4, 5, 5]);
+        assert_eq!(
+            vec![Some(1), Some(2), Some(2), Some(3), Some(0), Some(0)],
+            (0..6).map(|i| paths.dist(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(vec![5, 0, 2]), paths.path(2));
+    }
+
+    #[test]
+    fn zero_one_bfs() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 200;
+        let mut graph = Graph::new(n, 1000);
+        for _ in 0..1000 {
+            graph.connect(
+                rng.gen_range(0..n),
+                rng.gen_range(0..n),
+                rng.gen_range(0..2u32),
+            );
+        }
+        let paths = super::zero_one_bfs(&graph, 0);
+        let expected = dijkstra(&graph, 0);
+        for i in 0..n {
+            assert_eq!(expected.dist(i), paths.dist(i));
+        }
+    }
+}
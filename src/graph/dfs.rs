@@ -0,0 +1,224 @@
+use crate::data_structure::Graph;
+
+// Events of a depth-first search, all ignored by default.
+// Edges to nodes already exited (forward or cross edges) are not reported.
+// In an undirected graph, the edge back to the parent is a back edge.
+pub trait Visitor<T> {
+    fn enter(&mut self, _node: usize) {}
+    fn exit(&mut self, _node: usize) {}
+    fn tree_edge(&mut self, _from: usize, _to: usize, _edge: &T) {}
+    fn back_edge(&mut self, _from: usize, _to: usize, _edge: &T) {}
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    New,
+    Open,
+    Closed,
+}
+
+// Searches from each root not visited yet, in order, with an explicit stack.
+pub fn dfs<T, V: Visitor<T>>(
+    graph: &Graph<T>,
+    roots: impl IntoIterator<Item = usize>,
+    visitor: &mut V,
+) {
+    let mut state = vec![State::New; graph.node_count()];
+    let mut stack = vec![];
+    for root in roots {
+        if state[root] != State::New {
+            continue;
+        }
+        state[root] = State::Open;
+        visitor.enter(root);
+        stack.push((root, graph.neighbors(root)));
+        while let Some((now, neighbors)) = stack.last_mut() {
+            let now = *now;
+            let Some((next, edge)) = neighbors.next() else {
+                state[now] = State::Closed;
+                visitor.exit(now);
+                stack.pop();
+                continue;
+            };
+            match state[next] {
+                State::New => {
+                    visitor.tree_edge(now, next, edge);
+                    state[next] = State::Open;
+                    visitor.enter(next);
+                    stack.push((next, graph.neighbors(next)));
+                }
+                State::Open => visitor.back_edge(now, next, edge),
+                State::Closed => {}
+            }
+        }
+    }
+}
+
+// Orders and times of a depth-first search.
+// The subtree of v is preorder[entry(v)..exit(v)].
+pub struct DfsTree {
+    preorder: Vec<usize>,
+    postorder: Vec<usize>,
+    entry: Vec<Option<usize>>,
+    exit: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+}
+
+impl DfsTree {
+    pub fn new(n: usize) -> Self {
+        Self {
+            preorder: Vec::with_capacity(n),
+            postorder: Vec::with_capacity(n),
+            entry: vec![None; n],
+            exit: vec![None; n],
+            parent: vec![None; n],
+        }
+    }
+
+    pub fn preorder(&self) -> &[usize] {
+        &self.preorder
+    }
+
+    pub fn postorder(&self) -> &[usize] {
+        &self.postorder
+    }
+
+    // None if not visited
+    pub fn entry(&self, node: usize) -> Option<usize> {
+        self.entry[node]
+    }
+
+    // None if not visited
+    pub fn exit(&self, node: usize) -> Option<usize> {
+        self.exit[node]
+    }
+
+    // None for roots and nodes not visited
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parent[node]
+    }
+
+    // If u is an ancestor of v, or v itself
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        match (self.entry[u], self.exit[u], self.entry[v]) {
+            (Some(l), Some(r), Some(i)) => l <= i && i < r,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Visitor<T> for DfsTree {
+    fn enter(&mut self, node: usize) {
+        self.entry[node] = Some(self.preorder.len());
+        self.preorder.push(node);
+    }
+
+    fn exit(&mut self, node: usize) {
+        self.exit[node] = Some(self.preorder.len());
+        self.postorder.push(node);
+    }
+
+    fn tree_edge(&mut self, from: usize, to: usize, _edge: &T) {
+        self.parent[to] = Some(from);
+    }
+}
+
+pub fn dfs_tree<T>(graph: &Graph<T>, roots: impl IntoIterator<Item = usize>) -> DfsTree {
+    let mut tree = DfsTree::new(graph.node_count());
+    dfs(graph, roots, &mut tree);
+    tree
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Enter(usize),
+        Exit(usize),
+        Tree(usize, usize),
+        Back(usize, usize),
+    }
+
+    impl Visitor<()> for Vec<Event> {
+        fn enter(&mut self, node: usize) {
+            self.push(Event::Enter(node));
+        }
+        fn exit(&mut self, node: usize) {
+            self.push(Event::Exit(node));
+        }
+        fn tree_edge(&mut self, from: usize, to: usize, _edge: &()) {
+            self.push(Event::Tree(from, to));
+        }
+        fn back_edge(&mut self, from: usize, to: usize, _edge: &()) {
+            self.push(Event::Back(from, to));
+        }
+    }
+
+    fn test_graph() -> Graph<()> {
+        // Neighbors are iterated in reverse order of connection.
+        let mut graph = Graph::new(5, 5);
+        graph.connect(0, 2, ());
+        graph.connect(0, 1, ());
+        graph.connect(1, 0, ());
+        graph.connect(1, 2, ());
+        graph.connect(3, 2, ());
+        graph
+    }
+
+    #[test]
+    fn events() {
+        use Event::*;
+        let mut events = vec![];
+        dfs(&test_graph(), [0, 3, 1], &mut events);
+        assert_eq!(
+            vec![
+                Enter(0),
+                Tree(0, 1),
+                Enter(1),
+                Tree(1, 2),
+                Enter(2),
+                Exit(2),
+                Back(1, 0),
+                Exit(1),
+                Exit(0),
+                Enter(3),
+                Exit(3),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn dfs_tree() {
+        let tree = super::dfs_tree(&test_graph(), 0..5);
+        assert_eq!(&[0, 1, 2, 3, 4], tree.preorder());
+        assert_eq!(&[2, 1, 0, 3, 4], tree.postorder());
+        assert_eq!(
+            vec![None, Some(0), Some(1), None, None],
+            (0..5).map(|i| tree.parent(i)).collect::<Vec<_>>()
+        );
+        assert_eq!((Some(1), Some(3)), (tree.entry(1), tree.exit(1)));
+        assert!(tree.is_ancestor(0, 2));
+        assert!(tree.is_ancestor(2, 2));
+        assert!(!tree.is_ancestor(2, 1));
+        assert!(!tree.is_ancestor(0, 3));
+
+        let tree = super::dfs_tree(&test_graph(), [3]);
+        assert_eq!(&[3, 2], tree.preorder());
+        assert_eq!(None, tree.entry(0));
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 1_000_000;
+        let mut graph = Graph::new(n, n - 1);
+        for i in 1..n {
+            graph.connect(i - 1, i, ());
+        }
+        let tree = super::dfs_tree(&graph, [0]);
+        assert_eq!(Some(n - 2), tree.parent(n - 1));
+        assert_eq!(0, tree.postorder()[n - 1]);
+    }
+}
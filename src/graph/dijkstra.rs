@@ -0,0 +1,122 @@
+use crate::data_structure::Graph;
+use crate::graph::{ShortestPaths, Weight, WeightedEdge};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Weights must be non-negative.
+pub fn dijkstra<T: WeightedEdge>(graph: &Graph<T>, source: usize) -> ShortestPaths<T::Weight> {
+    let mut dist = vec![None; graph.node_count()];
+    let mut prev = vec![None; graph.node_count()];
+    let mut heap = BinaryHeap::new();
+    dist[source] = Some(T::Weight::ZERO);
+    heap.push(Reverse((T::Weight::ZERO, source)));
+    while let Some(Reverse((d, now))) = heap.pop() {
+        if dist[now] != Some(d) {
+            continue;
+        }
+        for (next, edge) in graph.neighbors(now) {
+            let nd = d + edge.weight();
+            if dist[next].is_none_or(|old| nd < old) {
+                dist[next] = Some(nd);
+                prev[next] = Some(now);
+                heap.push(Reverse((nd, next)));
+            }
+        }
+    }
+    ShortestPaths { dist, prev }
+}
+
+// O(V^2 + E) without a heap, for dense graphs.
+// Weights must be non-negative.
+pub fn dense_dijkstra<T: WeightedEdge>(
+    graph: &Graph<T>,
+    source: usize,
+) -> ShortestPaths<T::Weight> {
+    let n = graph.node_count();
+    let mut dist: Vec<Option<T::Weight>> = vec![None; n];
+    let mut prev = vec![None; n];
+    let mut done = vec![false; n];
+    dist[source] = Some(T::Weight::ZERO);
+    loop {
+        let mut now = None;
+        for i in 0..n {
+            if let (false, Some(d)) = (done[i], dist[i]) {
+                if now.is_none_or(|(_, best)| d < best) {
+                    now = Some((i, d));
+                }
+            }
+        }
+        let Some((now, d)) = now else {
+            break;
+        };
+        done[now] = true;
+        for (next, edge) in graph.neighbors(now) {
+            let nd = d + edge.weight();
+            if dist[next].is_none_or(|old| nd < old) {
+                dist[next] = Some(nd);
+                prev[next] = Some(now);
+            }
+        }
+    }
+    ShortestPaths { dist, prev }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_graph() -> Graph<u32> {
+        let mut graph = Graph::new(6, 9);
+        graph.connect(0, 1, 7);
+        graph.connect(0, 2, 9);
+        graph.connect(0, 5, 14);
+        graph.connect(1, 2, 10);
+        graph.connect(1, 3, 15);
+        graph.connect(2, 3, 11);
+        graph.connect(2, 5, 2);
+        graph.connect(5, 4, 9);
+        graph.connect(3, 4, 6);
+        graph
+    }
+
+    #[test]
+    fn dist() {
+        let paths = dijkstra(&test_graph(), 0);
+        assert_eq!(
+            vec![Some(0), Some(7), Some(9), Some(20), Some(20), Some(11)],
+            (0..6).map(|i| paths.dist(i)).collect::<Vec<_>>()
+        );
+        let paths = dijkstra(&test_graph(), 3);
+        assert_eq!(None, paths.dist(0));
+        assert_eq!(None, paths.path(0));
+        assert_eq!(Some(6), paths.dist(4));
+    }
+
+    #[test]
+    fn path() {
+        let paths = dijkstra(&test_graph(), 0);
+        assert_eq!(Some(vec![0, 2, 5, 4]), paths.path(4));
+        assert_eq!(Some(vec![0]), paths.path(0));
+        assert_eq!(Some(2), paths.prev(5));
+        assert_eq!(None, paths.prev(0));
+    }
+
+    #[test]
+    fn dense() {
+        let mut graph = Graph::new(30, 30 * 29);
+        for i in 0..30u64 {
+            for j in 0..30u64 {
+                if i != j {
+                    graph.connect(i as usize, j as usize, (i * 7 + j * 13) % 23 + 1);
+                }
+            }
+        }
+        for source in [0, 7, 29] {
+            let sparse = dijkstra(&graph, source);
+            let dense = dense_dijkstra(&graph, source);
+            for i in 0..30 {
+                assert_eq!(sparse.dist(i), dense.dist(i));
+            }
+        }
+    }
+}
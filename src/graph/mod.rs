@@ -0,0 +1,76 @@
+pub mod all_pairs;
+pub mod bellman_ford;
+pub mod bfs;
+pub mod dfs;
+pub mod dijkstra;
+pub mod mst;
+pub mod scc;
+pub mod two_sat;
+
+pub use all_pairs::{floyd_warshall, johnson, DistanceMatrix};
+pub use bellman_ford::{bellman_ford, DifferenceConstraints};
+pub use bfs::{bfs, multi_source_bfs, zero_one_bfs};
+pub use dfs::{dfs, dfs_tree, DfsTree, Visitor};
+pub use dijkstra::{dense_dijkstra, dijkstra};
+pub use mst::{boruvka, kruskal, minimum_spanning_tree, prim};
+pub use scc::{scc, StronglyConnectedComponents};
+pub use two_sat::{Literal, TwoSat};
+
+use std::ops::{Add, Sub};
+
+pub trait Weight: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// Edge data of a weighted Graph
+pub trait WeightedEdge {
+    type Weight: Weight;
+    fn weight(&self) -> Self::Weight;
+}
+
+impl<W: Weight> WeightedEdge for W {
+    type Weight = W;
+    fn weight(&self) -> W {
+        *self
+    }
+}
+
+pub struct ShortestPaths<W> {
+    dist: Vec<Option<W>>,
+    prev: Vec<Option<usize>>,
+}
+
+impl<W: Weight> ShortestPaths<W> {
+    // None if unreachable
+    pub fn dist(&self, node: usize) -> Option<W> {
+        self.dist[node]
+    }
+
+    // Previous node on the shortest path, None for sources and unreachable nodes
+    pub fn prev(&self, node: usize) -> Option<usize> {
+        self.prev[node]
+    }
+
+    // Nodes from the source to node
+    pub fn path(&self, node: usize) -> Option<Vec<usize>> {
+        self.dist[node]?;
+        let mut path = vec![node];
+        let mut now = node;
+        while let Some(prev) = self.prev[now] {
+            path.push(prev);
+            now = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
@@ -0,0 +1,195 @@
+use crate::data_structure::{Graph, SetForest};
+use crate::graph::{Weight, WeightedEdge};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Edges are undirected, so connecting them in either or both directions works.
+// Every function returns the total weight and the ids of the chosen edges,
+// which are the indices in Graph::edges().
+
+// Minimum spanning forest, by sorting the edges
+pub fn kruskal<T: WeightedEdge>(graph: &Graph<T>) -> (T::Weight, Vec<usize>) {
+    let edges = graph.edges();
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by_key(|&id| edges[id].2.weight());
+    let mut forest = SetForest::new(graph.node_count());
+    let mut total = T::Weight::ZERO;
+    let mut chosen = vec![];
+    for id in order {
+        let (u, v, edge) = edges[id];
+        if !forest.join(u, v) {
+            total = total + edge.weight();
+            chosen.push(id);
+        }
+    }
+    (total, chosen)
+}
+
+// Minimum spanning forest, growing a tree from each node not reached yet
+pub fn prim<T: WeightedEdge>(graph: &Graph<T>) -> (T::Weight, Vec<usize>) {
+    let n = graph.node_count();
+    let mut adj = vec![vec![]; n];
+    for (id, (u, v, edge)) in graph.edges().into_iter().enumerate() {
+        adj[u].push((v, id, edge.weight()));
+        adj[v].push((u, id, edge.weight()));
+    }
+    let mut done = vec![false; n];
+    let mut heap = BinaryHeap::new();
+    let mut total = T::Weight::ZERO;
+    let mut chosen = vec![];
+    for root in 0..n {
+        if done[root] {
+            continue;
+        }
+        done[root] = true;
+        heap.extend(
+            adj[root]
+                .iter()
+                .map(|&(next, id, w)| Reverse((w, id, next))),
+        );
+        while let Some(Reverse((w, id, now))) = heap.pop() {
+            if done[now] {
+                continue;
+            }
+            done[now] = true;
+            total = total + w;
+            chosen.push(id);
+            for &(next, id, w) in &adj[now] {
+                if !done[next] {
+                    heap.push(Reverse((w, id, next)));
+                }
+            }
+        }
+    }
+    (total, chosen)
+}
+
+// Minimum spanning forest, merging every component with its cheapest
+// outgoing edge in O(log V) rounds
+pub fn boruvka<T: WeightedEdge>(graph: &Graph<T>) -> (T::Weight, Vec<usize>) {
+    let edges: Vec<_> = graph
+        .edges()
+        .into_iter()
+        .map(|(u, v, edge)| (u, v, edge.weight()))
+        .collect();
+    let mut forest = SetForest::new(graph.node_count());
+    let mut total = T::Weight::ZERO;
+    let mut chosen = vec![];
+    loop {
+        // Ties are broken by id, so that the chosen edges never form a cycle.
+        let mut cheapest: Vec<Option<(T::Weight, usize)>> = vec![None; graph.node_count()];
+        for (id, &(u, v, w)) in edges.iter().enumerate() {
+            let (ru, rv) = (forest.find(u), forest.find(v));
+            if ru == rv {
+                continue;
+            }
+            for r in [ru, rv] {
+                if cheapest[r].is_none_or(|best| (w, id) < best) {
+                    cheapest[r] = Some((w, id));
+                }
+            }
+        }
+        let mut merged = false;
+        for (w, id) in cheapest.into_iter().flatten() {
+            let (u, v, _) = edges[id];
+            if !forest.join(u, v) {
+                total = total + w;
+                chosen.push(id);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    (total, chosen)
+}
+
+// None if the graph is disconnected
+pub fn minimum_spanning_tree<T: WeightedEdge>(graph: &Graph<T>) -> Option<(T::Weight, Vec<usize>)> {
+    let (total, chosen) = kruskal(graph);
+    if chosen.len() + 1 >= graph.node_count() {
+        Some((total, chosen))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn test_graph() -> Graph<u32> {
+        let mut graph = Graph::new(7, 11);
+        graph.connect(0, 1, 7);
+        graph.connect(0, 3, 5);
+        graph.connect(1, 2, 8);
+        graph.connect(1, 3, 9);
+        graph.connect(1, 4, 7);
+        graph.connect(2, 4, 5);
+        graph.connect(3, 4, 15);
+        graph.connect(3, 5, 6);
+        graph.connect(4, 5, 8);
+        graph.connect(4, 6, 9);
+        graph.connect(5, 6, 11);
+        graph
+    }
+
+    fn sorted((total, mut chosen): (u32, Vec<usize>)) -> (u32, Vec<usize>) {
+        chosen.sort_unstable();
+        (total, chosen)
+    }
+
+    #[test]
+    fn spanning_tree() {
+        let graph = test_graph();
+        let expected = (39, vec![0, 1, 4, 5, 7, 9]);
+        assert_eq!(expected, sorted(kruskal(&graph)));
+        assert_eq!(expected, sorted(prim(&graph)));
+        assert_eq!(expected, sorted(boruvka(&graph)));
+        assert_eq!(Some(expected), minimum_spanning_tree(&graph).map(sorted));
+    }
+
+    #[test]
+    fn spanning_forest() {
+        let mut graph = Graph::new(5, 5);
+        graph.connect(0, 1, 3);
+        graph.connect(1, 0, 1);
+        graph.connect(0, 0, 0);
+        graph.connect(3, 4, 2);
+        graph.connect(4, 3, 2);
+        for (total, chosen) in [kruskal(&graph), prim(&graph), boruvka(&graph)] {
+            assert_eq!(3, total);
+            assert_eq!(2, chosen.len());
+            assert!(chosen.contains(&1));
+        }
+        assert_eq!(None, minimum_spanning_tree(&graph));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let n = rng.gen_range(1..50);
+            let mut graph = Graph::new(n, 200);
+            for _ in 0..rng.gen_range(0..200) {
+                graph.connect(
+                    rng.gen_range(0..n),
+                    rng.gen_range(0..n),
+                    rng.gen_range(0..10),
+                );
+            }
+            let (total, chosen) = kruskal(&graph);
+            assert_eq!(total, prim(&graph).0);
+            assert_eq!(total, boruvka(&graph).0);
+            let edges = graph.edges();
+            assert_eq!(total, chosen.iter().map(|&id| edges[id].2).sum::<i64>());
+            let mut forest = SetForest::new(n);
+            for &(u, v, _) in &edges {
+                forest.join(u, v);
+            }
+            assert_eq!(n - forest.component_count(), chosen.len());
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::data_structure::Graph;
+
+pub struct StronglyConnectedComponents {
+    id: Vec<usize>,
+    count: usize,
+}
+
+impl StronglyConnectedComponents {
+    // Ids are in topological order, so every edge goes to the same or a larger id.
+    pub fn id(&self, node: usize) -> usize {
+        self.id[node]
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // Nodes of each component, in order of id
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut groups = vec![vec![]; self.count];
+        for (node, &id) in self.id.iter().enumerate() {
+            groups[id].push(node);
+        }
+        groups
+    }
+
+    // DAG of the components without self loops or parallel edges
+    pub fn condensation<T>(&self, graph: &Graph<T>) -> Graph<()> {
+        let mut edges: Vec<_> = graph
+            .edges()
+            .into_iter()
+            .map(|(u, v, _)| (self.id[u], self.id[v]))
+            .filter(|(u, v)| u != v)
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        let mut dag = Graph::new(self.count, edges.len());
+        for (u, v) in edges {
+            dag.connect(u, v, ());
+        }
+        dag
+    }
+}
+
+// Tarjan's algorithm with an explicit stack
+pub fn scc<T>(graph: &Graph<T>) -> StronglyConnectedComponents {
+    let n = graph.node_count();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut id = vec![usize::MAX; n];
+    let mut open = vec![];
+    let mut stack = vec![];
+    let mut visited = 0;
+    let mut count = 0;
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        index[root] = visited;
+        low[root] = visited;
+        visited += 1;
+        open.push(root);
+        stack.push((root, graph.neighbors(root)));
+        while let Some((now, neighbors)) = stack.last_mut() {
+            let now = *now;
+            if let Some((next, _)) = neighbors.next() {
+                if index[next] == usize::MAX {
+                    index[next] = visited;
+                    low[next] = visited;
+                    visited += 1;
+                    open.push(next);
+                    stack.push((next, graph.neighbors(next)));
+                } else if id[next] == usize::MAX {
+                    low[now] = low[now].min(index[next]);
+                }
+                continue;
+            }
+            stack.pop();
+            if let Some(&(parent, _)) = stack.last() {
+                low[parent] = low[parent].min(low[now]);
+            }
+            if low[now] == index[now] {
+                loop {
+                    let node = open.pop().unwrap();
+                    id[node] = count;
+                    if node == now {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+    // Components are found in reverse topological order.
+    for i in &mut id {
+        *i = count - 1 - *i;
+    }
+    StronglyConnectedComponents { id, count }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_graph() -> Graph<()> {
+        let mut graph = Graph::new(8, 11);
+        graph.connect(0, 1, ());
+        graph.connect(1, 2, ());
+        graph.connect(2, 0, ());
+        graph.connect(2, 3, ());
+        graph.connect(3, 4, ());
+        graph.connect(4, 3, ());
+        graph.connect(4, 5, ());
+        graph.connect(1, 5, ());
+        graph.connect(6, 5, ());
+        graph.connect(6, 0, ());
+        graph.connect(7, 7, ());
+        graph
+    }
+
+    #[test]
+    fn scc() {
+        let graph = test_graph();
+        let scc = super::scc(&graph);
+        assert_eq!(5, scc.count());
+        let mut groups = scc.groups();
+        groups.sort();
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![3, 4], vec![5], vec![6], vec![7]],
+            groups
+        );
+        for (u, v, _) in graph.edges() {
+            assert!(scc.id(u) <= scc.id(v));
+        }
+    }
+
+    #[test]
+    fn condensation() {
+        let graph = test_graph();
+        let scc = super::scc(&graph);
+        let dag = scc.condensation(&graph);
+        assert_eq!(5, dag.node_count());
+        let mut edges: Vec<_> = dag.edges().into_iter().map(|(u, v, _)| (u, v)).collect();
+        edges.sort();
+        let mut expected: Vec<_> = [(0, 3), (0, 5), (3, 5), (6, 0), (6, 5)]
+            .iter()
+            .map(|&(u, v)| (scc.id(u), scc.id(v)))
+            .collect();
+        expected.sort();
+        assert_eq!(expected, edges);
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 1_000_000;
+        let mut graph = Graph::new(n, n);
+        for i in 1..n {
+            graph.connect(i - 1, i, ());
+        }
+        let scc = super::scc(&graph);
+        assert_eq!(n, scc.count());
+        assert!((1..n).all(|i| scc.id(i - 1) < scc.id(i)));
+        graph.connect(n - 1, 0, ());
+        assert_eq!(1, super::scc(&graph).count());
+    }
+}
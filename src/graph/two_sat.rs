@@ -0,0 +1,166 @@
+use crate::data_structure::Graph;
+use crate::graph::scc;
+
+// Literal x[i] == value
+pub type Literal = (usize, bool);
+
+fn node((i, value): Literal) -> usize {
+    i << 1 | value as usize
+}
+
+fn not((i, value): Literal) -> Literal {
+    (i, !value)
+}
+
+pub struct TwoSat {
+    n: usize,
+    clauses: Vec<(Literal, Literal)>,
+}
+
+impl TwoSat {
+    pub fn new(n: usize) -> Self {
+        Self { n, clauses: vec![] }
+    }
+
+    pub fn var_count(&self) -> usize {
+        self.n
+    }
+
+    // Adds a variable, returning its index.
+    pub fn new_var(&mut self) -> usize {
+        self.n += 1;
+        self.n - 1
+    }
+
+    // a or b
+    pub fn either(&mut self, a: Literal, b: Literal) {
+        self.clauses.push((a, b));
+    }
+
+    // a implies b
+    pub fn implies(&mut self, a: Literal, b: Literal) {
+        self.either(not(a), b);
+    }
+
+    // Adds a variable per literal but the last, holding if any literal
+    // before it holds.
+    pub fn at_most_one(&mut self, literals: &[Literal]) {
+        let mut prefix: Option<Literal> = None;
+        for (i, &l) in literals.iter().enumerate() {
+            if let Some(p) = prefix {
+                self.implies(p, not(l));
+            }
+            if i + 1 < literals.len() {
+                let s = (self.new_var(), true);
+                self.implies(l, s);
+                if let Some(p) = prefix {
+                    self.implies(p, s);
+                }
+                prefix = Some(s);
+            }
+        }
+    }
+
+    // Exactly one of a and b. There is no list version, as at least one of
+    // three or more literals cannot be written in 2-SAT clauses.
+    pub fn exactly_one(&mut self, a: Literal, b: Literal) {
+        self.either(a, b);
+        self.either(not(a), not(b));
+    }
+
+    // A satisfying assignment of every variable, including those added by
+    // at_most_one, if any
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let mut graph = Graph::new(self.n * 2, self.clauses.len() * 2);
+        for &(a, b) in &self.clauses {
+            graph.connect(node(not(a)), node(b), ());
+            graph.connect(node(not(b)), node(a), ());
+        }
+        let scc = scc(&graph);
+        (0..self.n)
+            .map(|i| {
+                let (f, t) = (scc.id(node((i, false))), scc.id(node((i, true))));
+                // The literal later in topological order cannot imply its negation.
+                if f == t {
+                    None
+                } else {
+                    Some(f < t)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn satisfies(clauses: &[(Literal, Literal)], x: &[bool]) -> bool {
+        clauses
+            .iter()
+            .all(|&((i, a), (j, b))| x[i] == a || x[j] == b)
+    }
+
+    #[test]
+    fn solve() {
+        let mut sat = TwoSat::new(3);
+        sat.either((0, true), (1, false));
+        sat.implies((1, false), (2, true));
+        sat.either((0, false), (2, false));
+        let x = sat.solve().unwrap();
+        assert!(satisfies(&sat.clauses, &x));
+        sat.either((1, true), (1, true));
+        sat.implies((1, true), (0, false));
+        assert_eq!(None, sat.solve());
+    }
+
+    #[test]
+    fn at_most_one() {
+        let mut sat = TwoSat::new(5);
+        let literals = [(0, true), (1, true), (2, false), (3, true), (4, true)];
+        sat.at_most_one(&literals);
+        sat.either((3, true), (4, false));
+        let x = sat.solve().unwrap();
+        assert!(satisfies(&sat.clauses, &x));
+        assert!(literals.iter().filter(|&&(i, a)| x[i] == a).count() <= 1);
+        sat.either((0, true), (0, true));
+        assert!(sat.solve().is_some());
+        sat.either((2, false), (2, false));
+        assert_eq!(None, sat.solve());
+    }
+
+    #[test]
+    fn exactly_one() {
+        let mut sat = TwoSat::new(3);
+        sat.exactly_one((0, true), (1, true));
+        sat.either((1, false), (1, false));
+        sat.at_most_one(&[(0, true), (2, true)]);
+        assert_eq!(vec![true, false, false], sat.solve().unwrap()[..3]);
+        sat.exactly_one((2, true), (1, true));
+        assert_eq!(None, sat.solve());
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..8);
+            let mut sat = TwoSat::new(n);
+            for _ in 0..rng.gen_range(0..12) {
+                sat.either(
+                    (rng.gen_range(0..n), rng.gen()),
+                    (rng.gen_range(0..n), rng.gen()),
+                );
+            }
+            let brute = (0..1 << n)
+                .map(|mask| (0..n).map(|i| mask >> i & 1 == 1).collect::<Vec<_>>())
+                .any(|x| satisfies(&sat.clauses, &x));
+            let x = sat.solve();
+            assert_eq!(brute, x.is_some());
+            if let Some(x) = x {
+                assert!(satisfies(&sat.clauses, &x));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod data_structure;
+pub mod graph;
 pub mod math;
 pub mod string;
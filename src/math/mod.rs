@@ -4,6 +4,23 @@ pub trait Monoid {
     fn ident() -> Self::T;
 }
 
+// apply(l, r) == apply(r, l)
+pub trait CommutativeMonoid: Monoid {}
+
+// apply(a, inverse(a)) == ident()
+pub trait Group: Monoid {
+    fn inverse(a: &Self::T) -> Self::T;
+}
+
+// Monoid A acting on monoid M, where act(v, len, a) is applied to an
+// aggregate of len elements. Must satisfy
+// act(act(v, a), b) == act(v, A::apply(a, b)) and distribute over M::apply.
+pub trait Action {
+    type M: Monoid;
+    type A: Monoid;
+    fn act(v: &mut <Self::M as Monoid>::T, len: usize, a: &<Self::A as Monoid>::T);
+}
+
 pub fn power<M: Monoid>(a: &M::T, mut n: usize) -> M::T {
     let mut acc = M::ident();
     let mut mult = a.clone();
@@ -39,4 +56,76 @@ mod test {
     fn test_modular_power() {
         assert_eq!(844428231, power::<Mult>(&2, 63));
     }
+
+    struct Sum;
+
+    impl Monoid for Sum {
+        type T = i64;
+
+        fn apply(l: &i64, r: &i64) -> i64 {
+            l + r
+        }
+
+        fn ident() -> i64 {
+            0
+        }
+    }
+
+    impl CommutativeMonoid for Sum {}
+
+    struct Concat;
+
+    impl Monoid for Concat {
+        type T = Vec<usize>;
+
+        fn apply(l: &Vec<usize>, r: &Vec<usize>) -> Vec<usize> {
+            [l.as_slice(), r.as_slice()].concat()
+        }
+
+        fn ident() -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    struct AddToSum;
+
+    impl Action for AddToSum {
+        type M = Sum;
+        type A = Sum;
+
+        fn act(v: &mut i64, len: usize, a: &i64) {
+            *v += len as i64 * a;
+        }
+    }
+
+    #[test]
+    fn test_shared_monoid() {
+        use crate::data_structure::{FenwickTree, LazySegmentTree, SegmentTree};
+
+        let data = [2, 1, 4, 3, 6, 5, 8, 7, 10, 9];
+        assert_eq!(21, power::<Sum>(&3, 7));
+
+        let mut segtree = SegmentTree::<Sum>::new(10, data);
+        segtree.modify(5, &-5);
+        assert_eq!(43, segtree.query(3, 10));
+
+        let mut fenwick = FenwickTree::<Sum>::new(10);
+        for (i, v) in data.iter().enumerate() {
+            fenwick.modify(i + 1, v);
+        }
+        assert_eq!(10, fenwick.query(4));
+
+        let mut lazy = LazySegmentTree::<AddToSum>::new(10, data);
+        lazy.modify(5, 9, &-5);
+        assert_eq!(28, lazy.query(3, 10));
+
+        for n in [8, 10] {
+            let segtree = SegmentTree::<Concat>::new(n, (0..n).map(|i| vec![i]));
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!((l..r).collect::<Vec<_>>(), segtree.query(l, r));
+                }
+            }
+        }
+    }
 }
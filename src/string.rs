@@ -52,7 +52,7 @@ impl<'a, T: PartialEq> KmpMatcher<'a, T> {
         Self { pi, pattern }
     }
 
-    pub fn find<I: IntoIterator<Item = &'a T>>(&self, s: I) -> Kmp<I::IntoIter, T> {
+    pub fn find<I: IntoIterator<Item = &'a T>>(&self, s: I) -> Kmp<'_, I::IntoIter, T> {
         Kmp {
             matcher: self,
             i: s.into_iter(),